@@ -1,5 +1,45 @@
+// Whether draco is built with glTF and scene support (`transcoder` feature).
+fn transcoder_enabled() -> bool {
+    std::env::var_os("CARGO_FEATURE_TRANSCODER").is_some()
+}
+
+// Include directories of the third-party libraries used by the transcoder.
+// They are not bundled with this crate: they default to the locations of
+// draco's git submodules in `draco/third_party`, and can be overridden with
+// the same variables as in draco's CMake build.
+fn transcoder_dependencies() -> Vec<(&'static str, String)> {
+    let draco_root = format!("{}/draco", std::env::var("CARGO_MANIFEST_DIR").unwrap());
+    [
+        ("DRACO_EIGEN_PATH", "third_party/eigen"),
+        ("DRACO_FILESYSTEM_PATH", "third_party/filesystem/include"),
+        ("DRACO_TINYGLTF_PATH", "third_party/tinygltf"),
+    ]
+    .into_iter()
+    .map(|(var, default)| {
+        println!("cargo:rerun-if-env-changed={}", var);
+        let path = std::env::var(var).unwrap_or_else(|_| format!("{}/{}", draco_root, default));
+        // fail here rather than with an obscure error from draco's CMake
+        if !std::path::Path::new(&path).is_dir() {
+            panic!(
+                "the `transcoder` feature requires {} to point to a checkout of that \
+                 dependency, but {} does not exist",
+                var, path
+            );
+        }
+        (var, path)
+    })
+    .collect()
+}
+
 fn compile() -> String {
-    let dst = cmake::Config::new("draco")
+    let mut config = cmake::Config::new("draco");
+    if transcoder_enabled() {
+        config.define("DRACO_TRANSCODER_SUPPORTED", "ON");
+        for (var, path) in transcoder_dependencies() {
+            config.define(var, path);
+        }
+    }
+    let dst = config
         .define("CMAKE_BUILD_TYPE", "Release")
         .define("DRACO_POINT_CLOUD_COMPRESSION", "ON")
         .define("DRACO_MESH_COMPRESSION", "ON")
@@ -16,23 +56,38 @@ fn compile() -> String {
 }
 
 fn generate_bindings(out_dir: String) -> miette::Result<()> {
-    let includes = vec![
+    let mut includes = vec![
         "src".to_string(),
         "draco/src".to_string(),
         format!("{}/include", out_dir),
     ];
+    // the transcoder requires C++17
+    let std = if transcoder_enabled() {
+        includes.extend(transcoder_dependencies().into_iter().map(|(_, path)| path));
+        "c++17"
+    } else {
+        "c++14"
+    };
 
+    let std_flag = format!("-std={}", std);
     let mut b = autocxx_build::Builder::new("src/bindgen.rs", &includes)
         .extra_clang_args(&[
-            "-std=c++14",
+            std_flag.as_str(),
             "-w", // silences all warnings during clang parsing
             "-Wno-everything",
         ])
         .build()?;
 
+    // `DracoTranscoder` is only built into draco's command line tool, not
+    // into the library
+    if transcoder_enabled() {
+        b.file("draco/src/draco/tools/draco_transcoder_lib.cc");
+        println!("cargo:rerun-if-changed=draco/src/draco/tools/draco_transcoder_lib.cc");
+    }
+
     b.opt_level(3)
         .cpp(true)
-        .std("c++14")
+        .std(std)
         // .flag("-ldraco")
         // .flag("-Wl,-l:libdraco.a")
         // .flag(format!("-L{}", out_dir))
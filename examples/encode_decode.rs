@@ -1,5 +1,4 @@
 use draco_rs::prelude::*;
-use std::io;
 
 use draco_rs::pointcloud::{PointCloud, PointCloudBuilder};
 
@@ -20,47 +19,44 @@ fn gen_points() -> Vec<[f64; 3]> {
     ]
 }
 
-fn print_pc(pc: &mut PointCloud, attr_id: AttrId) {
-    let mut container: [f64; 3] = [0.0; 3];
+fn print_pc(pc: &PointCloud, attr_id: TypedAttrId<f64, 3>) {
     for i in 0..pc.len() {
-        pc.get_point(attr_id, i, &mut container);
-        println!("Point {}: {:?}", i, container);
+        match pc.get_point(attr_id, i) {
+            Ok(point) => println!("Point {}: {:?}", i, point),
+            Err(err) => println!("Point {}: {}", i, err),
+        }
     }
 }
 
-fn main() -> io::Result<()> {
+fn main() -> Result<(), Box<dyn std::error::Error>> {
     let points = gen_points();
 
     ////////// BUINDING //////////
     let mut builder = PointCloudBuilder::new(points.len() as u32);
 
-    let attr_id = builder.add_attribute(
-        ffi::draco::GeometryAttribute_Type::POSITION,
-        3,
-        ffi::draco::DataType::DT_FLOAT64,
-    );
+    let attr_id = builder.add_attribute::<f64, 3>(ffi::draco::GeometryAttribute_Type::POSITION)?;
 
     for (i, point) in points.iter().enumerate() {
-        builder.add_point(attr_id, i, point);
+        builder.add_point(attr_id, i, point)?;
     }
-    let mut pc = builder.build(false);
+    let pc = builder.build(false);
 
     println!("after building");
-    print_pc(&mut pc, attr_id);
+    print_pc(&pc, attr_id);
 
     ////////// ENCODE //////////
     let mut encoder = Encoder::new()
         .set_speed_options(5, 5)
         .set_attribute_quantization(ffi::draco::GeometryAttribute_Type::POSITION, 14);
 
-    if let Ok(mut buffer) = pc.to_buffer(&mut encoder) {
+    if let Ok(buffer) = pc.to_buffer(&mut encoder) {
         ////////// DECODE //////////
-        let mut buf = DecoderBuffer::from_encoder_buffer(&mut buffer);
+        let mut buf = DecoderBuffer::from_encoder_buffer(&buffer);
         let pc_decoded = PointCloud::from_buffer(&mut Decoder::new(), &mut buf);
 
-        if let Ok(mut pc_decoded) = pc_decoded {
+        if let Ok(pc_decoded) = pc_decoded {
             println!("after decoding");
-            print_pc(&mut pc_decoded, attr_id);
+            print_pc(&pc_decoded, attr_id);
         } else {
             println!("Failed to decode point cloud");
         }
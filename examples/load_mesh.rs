@@ -1,27 +1,23 @@
 use draco_rs::prelude::*;
+use draco_rs::{io::ReadOptions, mesh::Mesh};
 
-use cxx::let_cxx_string;
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("usage: load_mesh <mesh file (.ply, .obj, .stl or .drc)>")?;
 
-fn main() {
-    let_cxx_string!(fname = "/home/soraxas/git-repos/draco_ori/testdata/bun_zipper.ply");
-    let mut maybe_pc = ffi::draco::ReadMeshFromFile(&fname);
+    let mesh = Mesh::read_from_file_with_options(
+        &path,
+        ReadOptions {
+            use_metadata: true,
+            ..Default::default()
+        },
+    )?;
+    println!(
+        "loaded ok num faces: {}, num points: {}",
+        mesh.num_faces(),
+        mesh.num_points()
+    );
 
-    if let Some(pc) = maybe_pc.as_mut() {
-        if pc.ok() {
-            let mut mesh = pc.value();
-
-            // num_faces can already be accessed without mut
-            mesh.num_faces();
-
-            if let Some(mesh) = mesh.as_mut() {
-                println!("loaded ok num faces: {}", mesh.num_faces());
-            } else {
-                println!("Failed to get mesh mut");
-            }
-        } else {
-            println!("Draco error: {}", pc.error_msg());
-        }
-    } else {
-        println!("Failed to read mesh from file");
-    }
+    Ok(())
 }
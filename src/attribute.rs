@@ -1,17 +1,549 @@
-// use crate::prelude::ffi;
-
-// // Class for storing point specific data about each attribute. In general,
-// // multiple points stored in a point cloud can share the same attribute value
-// // and this class provides the necessary mapping between point ids and attribute
-// // value ids.
-// pub struct NonOwningPointAttribute {
-//     pub(crate) ptr: *const ffi::draco::PointAttribute,
-// }
-
-// impl NonOwningPointAttribute {
-//     // # Safety
-//     // This function is unsafe because it dereferences the raw pointer.
-//     pub unsafe fn size(&self) -> usize {
-//         (*self.ptr).size()
-//     }
-// }
+use crate::prelude::{ffi, AttrId};
+use autocxx::prelude::*;
+use std::marker::PhantomData;
+
+mod sealed {
+    pub trait Sealed {
+        // The in-memory representation draco uses for the component. This is
+        // what gets copied out of the attribute buffer before being converted
+        // into the Rust type (only differs for `bool`, where draco may store
+        // any byte value).
+        type Raw: Copy + Default;
+
+        // Whether the attribute buffer can be reinterpreted as `[Self]` directly.
+        const ZERO_COPY: bool = true;
+
+        fn from_raw(raw: Self::Raw) -> Self;
+    }
+}
+
+/// A Rust primitive that can be stored as a component of a draco attribute.
+///
+/// This trait is sealed and implemented for `i8`..`u64`, `f32`, `f64` and
+/// `bool`, each mapping to the matching `ffi::draco::DataType`.
+pub trait DracoComponent: sealed::Sealed + Copy + Default + 'static {
+    const DATA_TYPE: ffi::draco::DataType;
+}
+
+macro_rules! impl_draco_component {
+    ($($t:ty => $data_type:ident),* $(,)?) => {
+        $(
+            impl sealed::Sealed for $t {
+                type Raw = $t;
+
+                fn from_raw(raw: Self::Raw) -> Self {
+                    raw
+                }
+            }
+
+            impl DracoComponent for $t {
+                const DATA_TYPE: ffi::draco::DataType = ffi::draco::DataType::$data_type;
+            }
+        )*
+    };
+}
+
+impl_draco_component!(
+    i8 => DT_INT8,
+    u8 => DT_UINT8,
+    i16 => DT_INT16,
+    u16 => DT_UINT16,
+    i32 => DT_INT32,
+    u32 => DT_UINT32,
+    i64 => DT_INT64,
+    u64 => DT_UINT64,
+    f32 => DT_FLOAT32,
+    f64 => DT_FLOAT64,
+);
+
+impl sealed::Sealed for bool {
+    type Raw = u8;
+
+    const ZERO_COPY: bool = false;
+
+    fn from_raw(raw: Self::Raw) -> Self {
+        raw != 0
+    }
+}
+
+impl DracoComponent for bool {
+    const DATA_TYPE: ffi::draco::DataType = ffi::draco::DataType::DT_BOOL;
+}
+
+/// An attribute id that also records the component type `T` and the number
+/// of components `N` of the attribute it refers to.
+///
+/// Every access through a `TypedAttrId` is checked against the actual layout
+/// of the attribute, so reading or writing with the wrong type results in an
+/// [`AttributeError`] instead of memory corruption.
+pub struct TypedAttrId<T, const N: usize> {
+    id: AttrId,
+    _component: PhantomData<T>,
+}
+
+impl<T: DracoComponent, const N: usize> TypedAttrId<T, N> {
+    /// Attaches a type to an untyped attribute id, e.g. one obtained from
+    /// `get_named_attribute_id` on a decoded geometry. The type is verified
+    /// whenever the id is used.
+    pub fn new(id: AttrId) -> Self {
+        Self {
+            id,
+            _component: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> AttrId {
+        self.id
+    }
+}
+
+impl<T, const N: usize> TypedAttrId<T, N> {
+    // The number of components in the form draco takes it. draco stores it in
+    // an `i8`, so adding an attribute with a zero or too large `N` fails to
+    // compile.
+    pub(crate) const NUM_COMPONENTS: i8 = {
+        assert!(
+            N > 0 && N <= i8::MAX as usize,
+            "invalid number of attribute components"
+        );
+        N as i8
+    };
+}
+
+impl<T, const N: usize> Clone for TypedAttrId<T, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, const N: usize> Copy for TypedAttrId<T, N> {}
+
+impl<T, const N: usize> PartialEq for TypedAttrId<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T, const N: usize> Eq for TypedAttrId<T, N> {}
+
+impl<T, const N: usize> std::fmt::Debug for TypedAttrId<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "TypedAttrId<{}, {}>({})",
+            std::any::type_name::<T>(),
+            N,
+            self.id.0
+        )
+    }
+}
+
+impl<T, const N: usize> From<TypedAttrId<T, N>> for AttrId {
+    fn from(value: TypedAttrId<T, N>) -> Self {
+        value.id
+    }
+}
+
+// Returns whether draco keeps track of attributes of this type, i.e. whether
+// it can be used to add or look up attributes. `INVALID` and the
+// `NAMED_ATTRIBUTES_COUNT` sentinel are not real attribute types, and draco
+// does not always check for them.
+pub(crate) fn is_named_attribute_type(attribute_type: ffi::draco::GeometryAttribute_Type) -> bool {
+    !matches!(
+        attribute_type,
+        ffi::draco::GeometryAttribute_Type::INVALID
+            | ffi::draco::GeometryAttribute_Type::NAMED_ATTRIBUTES_COUNT
+    )
+}
+
+// Checks that `attr_id` refers to one of the `attributes` (data type and
+// number of components, indexed by attribute id) tracked by a builder, and
+// that its layout matches `T` and `N`.
+pub(crate) fn check_builder_attribute<T: DracoComponent, const N: usize>(
+    attributes: &[(ffi::draco::DataType, usize)],
+    attr_id: TypedAttrId<T, N>,
+) -> Result<(), AttributeError> {
+    let &(data_type, num_components) = usize::try_from(attr_id.id().0)
+        .ok()
+        .and_then(|i| attributes.get(i))
+        .ok_or(AttributeError::NotFound(attr_id.id()))?;
+    if data_type != T::DATA_TYPE {
+        return Err(AttributeError::DataTypeMismatch {
+            expected: T::DATA_TYPE,
+            actual: data_type,
+        });
+    }
+    if num_components != N {
+        return Err(AttributeError::ComponentCountMismatch {
+            expected: num_components,
+            actual: N,
+        });
+    }
+    Ok(())
+}
+
+/// Errors raised on the Rust side when accessing attribute data.
+#[derive(Debug)]
+pub enum AttributeError {
+    /// There is no attribute with the given id
+    NotFound(AttrId),
+    /// The component type does not match the data type of the attribute
+    DataTypeMismatch {
+        expected: ffi::draco::DataType,
+        actual: ffi::draco::DataType,
+    },
+    /// The number of components does not match the attribute
+    ComponentCountMismatch { expected: usize, actual: usize },
+    /// The point index is not covered by the attribute
+    PointOutOfRange { point: u32, num_points: u32 },
+    /// The face index is out of range
+    FaceOutOfRange { face: u32, num_faces: u32 },
+    /// draco refused to add an attribute of this type, e.g. because the
+    /// type is `INVALID` or the geometry has no points
+    NotAdded(ffi::draco::GeometryAttribute_Type),
+    /// The number of faces is larger than what draco supports
+    /// ([`MeshBuilder::MAX_NUM_FACES`](crate::mesh::MeshBuilder::MAX_NUM_FACES))
+    TooManyFaces(u32),
+    /// The number of face indices is not a multiple of three
+    InvalidIndexCount(usize),
+    /// The number of values does not match the number of points
+    ValueCountMismatch { expected: usize, actual: usize },
+    /// No value was given for the attribute while adding a point
+    MissingValue(AttrId),
+    /// The attribute values are not laid out as plain `T` values, e.g. the
+    /// attribute does not use identity mapping, or a strided field does not
+    /// lie within its vertex struct
+    NotContiguous,
+}
+
+impl std::fmt::Display for AttributeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound(id) => write!(f, "no attribute with id {}", id.0),
+            Self::DataTypeMismatch { expected, actual } => write!(
+                f,
+                "attribute has data type {:?} but {:?} was requested",
+                actual, expected
+            ),
+            Self::ComponentCountMismatch { expected, actual } => write!(
+                f,
+                "attribute has {} components but {} were given",
+                expected, actual
+            ),
+            Self::PointOutOfRange { point, num_points } => write!(
+                f,
+                "point {} is out of range (number of points: {})",
+                point, num_points
+            ),
+            Self::FaceOutOfRange { face, num_faces } => write!(
+                f,
+                "face {} is out of range (number of faces: {})",
+                face, num_faces
+            ),
+            Self::NotAdded(attribute_type) => {
+                write!(f, "unable to add an attribute of type {:?}", attribute_type)
+            }
+            Self::TooManyFaces(num_faces) => write!(
+                f,
+                "number of faces ({}) is larger than the maximum ({})",
+                num_faces,
+                crate::mesh::MeshBuilder::MAX_NUM_FACES
+            ),
+            Self::InvalidIndexCount(num_indices) => write!(
+                f,
+                "number of face indices ({}) is not a multiple of 3",
+                num_indices
+            ),
+            Self::ValueCountMismatch { expected, actual } => write!(
+                f,
+                "expected {} values (one per point) but {} were given",
+                expected, actual
+            ),
+            Self::MissingValue(id) => {
+                write!(f, "no value was given for attribute {}", id.0)
+            }
+            Self::NotContiguous => write!(f, "attribute values are not stored contiguously"),
+        }
+    }
+}
+
+impl std::error::Error for AttributeError {}
+
+// Class for storing point specific data about each attribute. In general,
+// multiple points stored in a point cloud can share the same attribute value
+// and this class provides the necessary mapping between point ids and attribute
+// value ids.
+//
+// The attribute is owned by its geometry (`PointCloud` or `Mesh`), so this
+// is only a borrowed view that cannot outlive the geometry it came from.
+#[derive(Clone, Copy)]
+pub struct PointAttributeRef<'a> {
+    inner: &'a ffi::draco::PointAttribute,
+}
+
+impl<'a> PointAttributeRef<'a> {
+    /// Wraps a raw attribute pointer handed out by draco.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must either be null or point to an attribute that stays alive
+    /// (and is not mutated) for the lifetime `'a`.
+    pub(crate) unsafe fn from_ptr(ptr: *const ffi::draco::PointAttribute) -> Option<Self> {
+        ptr.as_ref().map(|inner| Self { inner })
+    }
+
+    // autocxx does not expose base class methods, so the generic attribute
+    // properties are read through the `GeometryAttribute` part of the attribute.
+    fn geometry_attribute(&self) -> &'a ffi::draco::GeometryAttribute {
+        ffi::draco_extra::point_attribute_as_geometry_attribute(self.inner)
+    }
+
+    /// Returns the underlying draco attribute
+    pub fn get_inner(&self) -> &'a ffi::draco::PointAttribute {
+        self.inner
+    }
+
+    pub fn attribute_type(&self) -> ffi::draco::GeometryAttribute_Type {
+        self.geometry_attribute().attribute_type()
+    }
+
+    pub fn data_type(&self) -> ffi::draco::DataType {
+        self.geometry_attribute().data_type()
+    }
+
+    pub fn num_components(&self) -> u8 {
+        self.geometry_attribute().num_components()
+    }
+
+    pub fn normalized(&self) -> bool {
+        self.geometry_attribute().normalized()
+    }
+
+    pub fn unique_id(&self) -> u32 {
+        self.geometry_attribute().unique_id()
+    }
+
+    // Returns the size in bytes of a single attribute value.
+    pub fn byte_stride(&self) -> usize {
+        self.geometry_attribute().byte_stride() as usize
+    }
+
+    // Returns the number of unique attribute values stored in the attribute.
+    pub fn size(&self) -> usize {
+        self.inner.size()
+    }
+
+    // Returns true when the i-th point maps directly onto the i-th attribute value.
+    pub fn is_mapping_identity(&self) -> bool {
+        self.inner.is_mapping_identity()
+    }
+
+    // Returns the number of points covered by the explicit point-to-value
+    // mapping (always 0 for identity mappings).
+    pub fn indices_map_size(&self) -> usize {
+        self.inner.indices_map_size()
+    }
+
+    // Returns the number of points that can be looked up in this attribute.
+    fn num_mapped_points(&self) -> usize {
+        if self.is_mapping_identity() {
+            self.size()
+        } else {
+            self.indices_map_size()
+        }
+    }
+
+    /// Returns the index of the attribute value used by the given point, or
+    /// `None` if the point is not covered by this attribute.
+    pub fn mapped_index(&self, point_index: impl Into<ffi::draco::PointIndex>) -> Option<u32> {
+        let point_index = point_index.into();
+        if point_index.val as usize >= self.num_mapped_points() {
+            return None;
+        }
+        let value_index = self.inner.mapped_index(point_index).val;
+        if value_index as usize >= self.size() {
+            return None;
+        }
+        Some(value_index)
+    }
+
+    /// Checks that the attribute stores `num_components` components of type `T`.
+    pub fn check_layout<T: DracoComponent>(
+        &self,
+        num_components: usize,
+    ) -> Result<(), AttributeError> {
+        if self.data_type() != T::DATA_TYPE {
+            return Err(AttributeError::DataTypeMismatch {
+                expected: T::DATA_TYPE,
+                actual: self.data_type(),
+            });
+        }
+        if self.num_components() as usize != num_components {
+            return Err(AttributeError::ComponentCountMismatch {
+                expected: self.num_components() as usize,
+                actual: num_components,
+            });
+        }
+        // draco copies whole strides, so padded (interleaved) attributes
+        // cannot be read into a tightly packed value.
+        if self.byte_stride() != num_components * std::mem::size_of::<T::Raw>() {
+            return Err(AttributeError::ComponentCountMismatch {
+                expected: self.byte_stride() / std::mem::size_of::<T::Raw>(),
+                actual: num_components,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the attribute value of the given point.
+    ///
+    /// Fails if `T` and `N` do not match the layout of the attribute, or the
+    /// point is not covered by this attribute.
+    pub fn get_value<T: DracoComponent, const N: usize>(
+        &self,
+        point_index: impl Into<ffi::draco::PointIndex>,
+    ) -> Result<[T; N], AttributeError> {
+        self.check_layout::<T>(N)?;
+        let point_index = point_index.into().val;
+        if self.mapped_index(point_index).is_none() {
+            return Err(AttributeError::PointOutOfRange {
+                point: point_index,
+                num_points: self.num_mapped_points() as u32,
+            });
+        }
+        let mut raw = [T::Raw::default(); N];
+        unsafe {
+            self.inner
+                .GetMappedValue(point_index.into(), raw.as_mut_ptr() as *mut c_void);
+        }
+        Ok(raw.map(T::from_raw))
+    }
+
+    /// Copies the values of all points into a vector, in point order.
+    ///
+    /// This crosses the FFI boundary once for the whole attribute, which is
+    /// much faster than calling [`Self::get_value`] for every point.
+    pub fn get_values<T: DracoComponent, const N: usize>(
+        &self,
+    ) -> Result<Vec<[T; N]>, AttributeError> {
+        self.check_layout::<T>(N)?;
+        let num_points = self.num_mapped_points();
+        let mut raw = vec![[T::Raw::default(); N]; num_points];
+        let invalid_point = unsafe {
+            ffi::draco_extra::point_attribute_copy_mapped_values(
+                self.inner,
+                num_points as u32,
+                raw.as_mut_ptr() as *mut u8,
+            )
+        };
+        if invalid_point >= 0 {
+            return Err(AttributeError::PointOutOfRange {
+                point: invalid_point as u32,
+                num_points: num_points as u32,
+            });
+        }
+        Ok(raw
+            .into_iter()
+            .map(|value| value.map(T::from_raw))
+            .collect())
+    }
+
+    /// Borrows the attribute values in place, without copying.
+    ///
+    /// The returned slice holds `num_components` consecutive components for
+    /// each point. This is only possible for identity-mapped attributes whose
+    /// buffer holds plain `T` values; otherwise [`AttributeError::NotContiguous`]
+    /// is returned and [`Self::get_values`] should be used instead.
+    pub fn as_slice<T: DracoComponent>(&self) -> Result<&'a [T], AttributeError> {
+        self.check_layout::<T>(self.num_components() as usize)?;
+        if !T::ZERO_COPY || !self.is_mapping_identity() {
+            return Err(AttributeError::NotContiguous);
+        }
+        if self.size() == 0 {
+            return Ok(&[]);
+        }
+        let data = ffi::draco_extra::point_attribute_values_data(self.inner);
+        if data.is_null() || data.align_offset(std::mem::align_of::<T>()) != 0 {
+            return Err(AttributeError::NotContiguous);
+        }
+        // The buffer is owned by the attribute, which outlives 'a, and
+        // `point_attribute_values_data` checked that it holds `size()` values.
+        Ok(unsafe {
+            std::slice::from_raw_parts(
+                data as *const T,
+                self.size() * self.num_components() as usize,
+            )
+        })
+    }
+
+    /// Returns the quantization parameters of an attribute that was decoded
+    /// with [`Decoder::set_skip_attribute_transform`](crate::decode::Decoder::set_skip_attribute_transform),
+    /// or `None` if the attribute holds dequantized values.
+    ///
+    /// The values of such an attribute are the raw quantized integers; they can
+    /// be mapped back with [`QuantizationParameters::dequantize`].
+    pub fn quantization_parameters(&self) -> Option<QuantizationParameters> {
+        let mut quantization_bits = 0;
+        let mut range = 0.0;
+        let mut min_values = vec![0.0f32; self.num_components() as usize];
+        let initialized = unsafe {
+            ffi::draco_extra::point_attribute_quantization_parameters(
+                self.inner,
+                &mut quantization_bits,
+                &mut range,
+                min_values.as_mut_ptr(),
+            )
+        };
+        initialized.then_some(QuantizationParameters {
+            quantization_bits,
+            range,
+            min_values,
+        })
+    }
+}
+
+/// Parameters of the quantization transform applied by the encoder to an
+/// attribute.
+///
+/// A quantized component `q` maps back to
+/// `min_values[axis] + q * range / (2^quantization_bits - 1)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuantizationParameters {
+    /// Number of bits used for each quantized component.
+    pub quantization_bits: i32,
+    /// Size of the bounding cube of the original values.
+    pub range: f32,
+    /// Minimum of the original values, one entry per component.
+    pub min_values: Vec<f32>,
+}
+
+impl QuantizationParameters {
+    /// Returns the difference between two consecutive quantized values.
+    pub fn delta(&self) -> f32 {
+        let max_quantized_value = (1u64 << self.quantization_bits.clamp(0, 32)) - 1;
+        if max_quantized_value == 0 {
+            return 0.0;
+        }
+        self.range / max_quantized_value as f32
+    }
+
+    /// Maps a quantized value back to its original (approximate) value.
+    pub fn dequantize<const N: usize>(&self, value: [u32; N]) -> [f32; N] {
+        let delta = self.delta();
+        std::array::from_fn(|axis| {
+            self.min_values.get(axis).copied().unwrap_or(0.0) + value[axis] as f32 * delta
+        })
+    }
+}
+
+impl std::fmt::Debug for PointAttributeRef<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PointAttributeRef")
+            .field("unique_id", &self.unique_id())
+            .field("num_components", &self.num_components())
+            .field("normalized", &self.normalized())
+            .field("size", &self.size())
+            .field("is_mapping_identity", &self.is_mapping_identity())
+            .finish()
+    }
+}
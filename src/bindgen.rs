@@ -27,6 +27,7 @@ autocxx::include_cpp! {
     #include "draco/compression/decode.h"
     #include "draco/compression/expert_encode.h"
     #include "draco/point_cloud/point_cloud_builder.h"
+    #include "draco/mesh/triangle_soup_mesh_builder.h"
     #include "draco/core/encoder_buffer.h"
     #include "draco/core/cycle_timer.h"
     #include "draco/io/file_utils.h"
@@ -34,6 +35,11 @@ autocxx::include_cpp! {
     #include "draco/io/point_cloud_io.h"
 
     #include "draco/io/stdio_file_reader.h"
+    #include "draco/io/stdio_file_writer.h"
+    // only declare anything when draco is built with the `transcoder` feature
+    #include "draco/io/gltf_decoder.h"
+    #include "draco/io/gltf_encoder.h"
+    #include "draco/io/scene_io.h"
     #include "extra.h"
 
     // Safety policy. We are marking that this whole C++ inclusion is unsafe
@@ -56,10 +62,11 @@ autocxx::include_cpp! {
     // generate!("draco::Status")
 
     ////////////////////////////////////////////////////////////////////////////////
-    // force the rust bind to generate file reader translation unit
+    // force the rust bind to generate file reader and writer translation units
     // because they are using a static register function to register the file reader (sigh)
     // (no longer neede if we pull in the whole namespace)
     generate!("draco::StdioFileReader")
+    generate!("draco::StdioFileWriter")
     ////////////////////////////////////////////////////////////////////////////////
 
     generate_pod!("draco::PointIndexIndexType")
@@ -16,7 +16,7 @@
 use autocxx::prelude::*;
 use std::pin::Pin;
 
-use crate::prelude::ffi;
+use crate::prelude::{ffi, DracoError, DracoResult, Operation};
 
 pub trait StatusOr<T> {
     fn ok(&self) -> bool {
@@ -35,6 +35,12 @@ pub trait StatusOr<T> {
 
     // to be implemented by the mangled cxx template type
     fn value(self: Pin<&mut Self>) -> T;
+
+    // Unpacks the value, or converts the status into a `DracoError`
+    fn into_result(self: Pin<&mut Self>, operation: Operation) -> DracoResult<T> {
+        DracoError::check(&self.status().within_box(), operation)?;
+        Ok(self.value())
+    }
 }
 
 impl StatusOr<UniquePtr<ffi::draco::Mesh>>
@@ -49,6 +55,32 @@ impl StatusOr<UniquePtr<ffi::draco::Mesh>>
     }
 }
 
+#[cfg(feature = "transcoder")]
+impl StatusOr<UniquePtr<ffi::draco::Scene>>
+    for ffi::draco_StatusOr_std_unique_ptr_draco_Scene_AutocxxConcrete
+{
+    fn status(&self) -> impl New<Output = ffi::draco::Status> {
+        ffi::draco_extra::unpack_status_or_scene_status(self)
+    }
+
+    fn value(self: Pin<&mut Self>) -> UniquePtr<ffi::draco::Scene> {
+        ffi::draco_extra::unpack_status_or_scene_value(self)
+    }
+}
+
+#[cfg(feature = "transcoder")]
+impl StatusOr<UniquePtr<ffi::draco::DracoTranscoder>>
+    for ffi::draco_StatusOr_std_unique_ptr_draco_DracoTranscoder_AutocxxConcrete
+{
+    fn status(&self) -> impl New<Output = ffi::draco::Status> {
+        ffi::draco_extra::unpack_status_or_transcoder_status(self)
+    }
+
+    fn value(self: Pin<&mut Self>) -> UniquePtr<ffi::draco::DracoTranscoder> {
+        ffi::draco_extra::unpack_status_or_transcoder_value(self)
+    }
+}
+
 impl StatusOr<UniquePtr<ffi::draco::PointCloud>>
     for ffi::draco_StatusOr_std_unique_ptr_draco_PointCloud_AutocxxConcrete
 {
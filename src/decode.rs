@@ -1,30 +1,62 @@
-use crate::{encode::EncoderBuffer, prelude::*};
+use crate::{
+    encode::EncoderBuffer, geometry::Geometry, mesh::Mesh, pointcloud::PointCloud, prelude::*,
+};
 use autocxx::prelude::*;
+use std::marker::PhantomData;
 
-pub type DecoderBuffer = WrappedDracoObject<ffi::draco::DecoderBuffer>;
+/// A read-only view over encoded draco data.
+///
+/// draco's `DecoderBuffer` only stores a pointer to the data it decodes, so
+/// the buffer either borrows its input for `'a`, or owns it (see
+/// [`DecoderBuffer::from_owned`]).
+pub struct DecoderBuffer<'a> {
+    buffer: UniquePtr<ffi::draco::DecoderBuffer>,
+    // keeps the input alive when the buffer owns it; the heap allocation of a
+    // vector does not move with the vector, so the pointer held by draco
+    // stays valid
+    _owned: Option<Vec<u8>>,
+    _data: PhantomData<&'a [u8]>,
+}
 
-impl DecoderBuffer {
+impl DecoderBuffer<'static> {
     pub fn new() -> Self {
-        Self(ffi::draco::DecoderBuffer::new().within_unique_ptr())
+        Self {
+            buffer: ffi::draco::DecoderBuffer::new().within_unique_ptr(),
+            _owned: None,
+            _data: PhantomData,
+        }
+    }
+
+    /// Initializes the decoder buffer with data it takes ownership of
+    pub fn from_owned(data: impl Into<Vec<u8>>) -> Self {
+        let data = data.into();
+        let mut buffer = unsafe {
+            DecoderBuffer::from_buffer_ptr(data.as_ptr() as *const std::os::raw::c_char, data.len())
+        };
+        buffer._owned = Some(data);
+        buffer
     }
+}
 
+impl<'a> DecoderBuffer<'a> {
     /// Initializes the decoder buffer with a raw data pointer and size
     ///
     /// # Safety
     ///
-    /// The data pointer must be valid for the lifetime of the decoder buffer
+    /// The data pointer must be valid for the lifetime `'a`
     pub unsafe fn from_buffer_ptr(data_ptr: *const std::os::raw::c_char, size: usize) -> Self {
-        let mut buffer = Self::new();
-        buffer.0.pin_mut().Init(data_ptr, size);
-        buffer
+        let mut buffer = ffi::draco::DecoderBuffer::new().within_unique_ptr();
+        buffer.pin_mut().Init(data_ptr, size);
+        Self {
+            buffer,
+            _owned: None,
+            _data: PhantomData,
+        }
     }
 
-    /// Initializes the decoder buffer with a slice of data
-    ///
-    /// # Safety
-    ///
-    /// The given slice must be valid for the lifetime of the decoder buffer
-    pub fn from_buffer(data_buffer: &[u8]) -> Self {
+    /// Initializes the decoder buffer with a slice of data, which stays
+    /// borrowed for as long as the decoder buffer is alive
+    pub fn from_buffer(data_buffer: &'a [u8]) -> Self {
         unsafe {
             Self::from_buffer_ptr(
                 data_buffer.as_ptr() as *const std::os::raw::c_char,
@@ -34,26 +66,98 @@ impl DecoderBuffer {
     }
 
     /// Initializes the decoder buffer with the data from the encoder buffer
-    pub fn from_encoder_buffer(encoder_buffer: &mut EncoderBuffer) -> Self {
+    pub fn from_encoder_buffer(encoder_buffer: &'a EncoderBuffer) -> Self {
         Self::from_buffer(encoder_buffer.as_slice())
     }
+
+    pub fn as_mut_ptr(&mut self) -> *mut ffi::draco::DecoderBuffer {
+        self.buffer.as_mut_ptr()
+    }
+
+    /// Returns the number of bytes that have not been decoded yet
+    pub fn remaining_size(&self) -> i64 {
+        self.buffer.remaining_size()
+    }
 }
 
-impl Default for DecoderBuffer {
+impl Default for DecoderBuffer<'static> {
     fn default() -> Self {
         Self::new()
     }
 }
 
+// `DecoderBuffer` only hands out shared references to the underlying draco
+// object, as mutating it could point it at data that does not live for `'a`.
+impl DecoderBuffer<'_> {
+    pub fn get_inner(&self) -> &UniquePtr<ffi::draco::DecoderBuffer> {
+        &self.buffer
+    }
+}
+
 pub struct Decoder {
     pub(crate) decoder: UniquePtr<ffi::draco::Decoder>,
 }
 
+/// The kind of geometry stored in a draco bitstream
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EncodedGeometryType {
+    PointCloud,
+    TriangularMesh,
+}
+
 impl Decoder {
     pub fn new() -> Self {
         let decoder = ffi::draco::Decoder::new().within_unique_ptr();
         Self { decoder }
     }
+
+    /// Skips the given attribute transform when decoding attributes of type
+    /// `attr_type`. For quantized attributes, the decoded attribute then holds
+    /// the quantized integer values; the parameters needed to dequantize them
+    /// are available from [`PointAttributeRef::quantization_parameters`].
+    pub fn set_skip_attribute_transform(
+        mut self,
+        attr_type: ffi::draco::GeometryAttribute_Type,
+    ) -> Self {
+        self.decoder.pin_mut().SetSkipAttributeTransform(attr_type);
+        self
+    }
+
+    /// Reads the header of the encoded data to find out which kind of
+    /// geometry it contains. The buffer is not consumed.
+    pub fn get_encoded_geometry_type(
+        buffer: &mut DecoderBuffer,
+    ) -> DracoResult<EncodedGeometryType> {
+        let mut geometry_type = -1;
+        let status = unsafe {
+            ffi::draco_extra::decoder_get_encoded_geometry_type(
+                buffer.as_mut_ptr(),
+                &mut geometry_type,
+            )
+            .within_box()
+        };
+        DracoError::check(&status, Operation::Decode)?;
+        match geometry_type {
+            0 => Ok(EncodedGeometryType::PointCloud),
+            1 => Ok(EncodedGeometryType::TriangularMesh),
+            _ => Err(DracoError::UnsupportedFeature {
+                operation: Operation::Decode,
+                message: format!("Unsupported geometry type {}.", geometry_type),
+            }),
+        }
+    }
+
+    /// Decodes a point cloud or a mesh, depending on what the buffer contains.
+    pub fn decode(&mut self, buffer: &mut DecoderBuffer<'_>) -> DracoResult<Geometry> {
+        match Self::get_encoded_geometry_type(buffer)? {
+            EncodedGeometryType::PointCloud => {
+                PointCloud::from_buffer(self, buffer).map(Geometry::PointCloud)
+            }
+            EncodedGeometryType::TriangularMesh => {
+                Mesh::from_buffer(self, buffer).map(Geometry::Mesh)
+            }
+        }
+    }
 }
 impl Default for Decoder {
     fn default() -> Self {
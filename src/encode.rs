@@ -1,5 +1,75 @@
-use crate::prelude::*;
+use crate::{mesh::Mesh, pointcloud::PointCloud, prelude::*};
 use autocxx::prelude::*;
+use std::marker::PhantomData;
+
+/// Encoding methods for point clouds (`draco::PointCloudEncodingMethod`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PointCloudEncodingMethod {
+    Sequential = 0,
+    KdTree = 1,
+}
+
+/// Encoding methods for meshes (`draco::MeshEncoderMethod`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MeshEncoderMethod {
+    Sequential = 0,
+    Edgebreaker = 1,
+}
+
+/// Connectivity encoding submethods for [`MeshEncoderMethod::Edgebreaker`]
+/// (`draco::MeshEdgebreakerConnectivityEncodingMethod`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MeshEdgebreakerMethod {
+    Standard = 0,
+    Valence = 2,
+}
+
+/// An encoding method for either kind of geometry. Selecting a method that
+/// does not match the encoded geometry makes the encoding fail.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum EncodingMethod {
+    PointCloud(PointCloudEncodingMethod),
+    Mesh(MeshEncoderMethod),
+}
+
+impl EncodingMethod {
+    fn as_raw(self) -> i32 {
+        match self {
+            Self::PointCloud(method) => method as i32,
+            Self::Mesh(method) => method as i32,
+        }
+    }
+}
+
+impl From<PointCloudEncodingMethod> for EncodingMethod {
+    fn from(method: PointCloudEncodingMethod) -> Self {
+        Self::PointCloud(method)
+    }
+}
+
+impl From<MeshEncoderMethod> for EncodingMethod {
+    fn from(method: MeshEncoderMethod) -> Self {
+        Self::Mesh(method)
+    }
+}
+
+/// Prediction schemes used to encode attribute values
+/// (`draco::PredictionSchemeMethod`, without the deprecated entries)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PredictionSchemeMethod {
+    /// No prediction
+    None = -2,
+    /// Delta coding
+    Difference = 0,
+    /// Parallelogram prediction (meshes only)
+    MeshParallelogram = 1,
+    /// Better and more costly version of the parallelogram prediction (meshes only)
+    MeshConstrainedMultiParallelogram = 4,
+    /// Texture coordinate prediction (meshes and `TEX_COORD` attributes only)
+    MeshTexCoordsPortable = 5,
+    /// Normal prediction (meshes and `NORMAL` attributes only)
+    MeshGeometricNormal = 6,
+}
 
 pub type Encoder = WrappedDracoObject<ffi::draco::Encoder>;
 
@@ -20,12 +90,76 @@ impl Encoder {
         self
     }
 
+    /// Quantizes the values of attributes of the given type to `num_bits`
+    /// bits, within the box spanning from `origin` to `origin + range` in
+    /// every dimension.
+    pub fn set_attribute_explicit_quantization(
+        mut self,
+        attr: ffi::draco::GeometryAttribute_Type,
+        num_bits: i32,
+        origin: &[f32],
+        range: f32,
+    ) -> Self {
+        unsafe {
+            self.0.pin_mut().SetAttributeExplicitQuantization(
+                attr,
+                num_bits.into(),
+                (origin.len() as i32).into(),
+                origin.as_ptr(),
+                range,
+            );
+        }
+        self
+    }
+
+    pub fn set_attribute_prediction_scheme(
+        mut self,
+        attr: ffi::draco::GeometryAttribute_Type,
+        prediction_scheme: PredictionSchemeMethod,
+    ) -> DracoResult<Self> {
+        let status = self
+            .0
+            .pin_mut()
+            .SetAttributePredictionScheme(attr, (prediction_scheme as i32).into())
+            .within_box();
+        DracoError::check(&status, Operation::Encode)?;
+        Ok(self)
+    }
+
     pub fn set_speed_options(mut self, encoding_speed: i32, decoding_speed: i32) -> Self {
         self.0
             .pin_mut()
             .SetSpeedOptions(encoding_speed.into(), decoding_speed.into());
         self
     }
+
+    /// Selects the encoding method (sequential, kd-tree or edgebreaker)
+    /// instead of letting draco choose one based on the speed options.
+    pub fn set_encoding_method(mut self, method: impl Into<EncodingMethod>) -> Self {
+        self.0
+            .pin_mut()
+            .SetEncodingMethod(method.into().as_raw().into());
+        self
+    }
+
+    /// Makes the encoder record the number of encoded points and faces, see
+    /// [`Self::num_encoded_points`] and [`Self::num_encoded_faces`].
+    pub fn set_track_encoded_properties(mut self, flag: bool) -> Self {
+        ffi::draco_extra::encoder_set_track_encoded_properties(self.0.pin_mut(), flag);
+        self
+    }
+
+    /// Returns the number of points encoded by the last encoding call, if
+    /// tracking was enabled with [`Self::set_track_encoded_properties`].
+    pub fn num_encoded_points(&self) -> usize {
+        ffi::draco_extra::encoder_num_encoded_points(&self.0)
+    }
+
+    /// Returns the number of faces encoded by the last encoding call, if
+    /// tracking was enabled with [`Self::set_track_encoded_properties`].
+    pub fn num_encoded_faces(&self) -> usize {
+        ffi::draco_extra::encoder_num_encoded_faces(&self.0)
+    }
 }
 impl Default for Encoder {
     fn default() -> Self {
@@ -33,6 +167,133 @@ impl Default for Encoder {
     }
 }
 
+/// An encoder bound to a single geometry, which allows setting options per
+/// attribute id rather than per attribute type (e.g. different quantization
+/// for two `GENERIC` attributes).
+pub struct ExpertEncoder<'a> {
+    encoder: UniquePtr<ffi::draco::ExpertEncoder>,
+    // the draco encoder keeps a pointer to the geometry
+    _geometry: PhantomData<&'a ffi::draco::PointCloud>,
+}
+
+impl<'a> ExpertEncoder<'a> {
+    pub fn from_point_cloud(pc: &'a PointCloud) -> Self {
+        Self {
+            encoder: ffi::draco_extra::new_expert_encoder_for_point_cloud(pc.as_point_cloud()),
+            _geometry: PhantomData,
+        }
+    }
+
+    pub fn from_mesh(mesh: &'a Mesh) -> Self {
+        Self {
+            encoder: ffi::draco_extra::new_expert_encoder_for_mesh(&mesh.0),
+            _geometry: PhantomData,
+        }
+    }
+
+    pub fn set_speed_options(mut self, encoding_speed: i32, decoding_speed: i32) -> Self {
+        self.encoder
+            .pin_mut()
+            .SetSpeedOptions(encoding_speed.into(), decoding_speed.into());
+        self
+    }
+
+    /// Quantizes the values of the given attribute to `num_bits` bits, within
+    /// the bounding box of the attribute values.
+    pub fn set_attribute_quantization(mut self, attr_id: AttrId, num_bits: i32) -> Self {
+        self.encoder
+            .pin_mut()
+            .SetAttributeQuantization(attr_id.0, num_bits.into());
+        self
+    }
+
+    /// Quantizes the values of the given attribute to `num_bits` bits, within
+    /// the box spanning from `origin` to `origin + range` in every dimension.
+    pub fn set_attribute_explicit_quantization(
+        mut self,
+        attr_id: AttrId,
+        num_bits: i32,
+        origin: &[f32],
+        range: f32,
+    ) -> Self {
+        unsafe {
+            self.encoder.pin_mut().SetAttributeExplicitQuantization(
+                attr_id.0,
+                num_bits.into(),
+                (origin.len() as i32).into(),
+                origin.as_ptr(),
+                range,
+            );
+        }
+        self
+    }
+
+    pub fn set_attribute_prediction_scheme(
+        mut self,
+        attr_id: AttrId,
+        prediction_scheme: PredictionSchemeMethod,
+    ) -> DracoResult<Self> {
+        let status = self
+            .encoder
+            .pin_mut()
+            .SetAttributePredictionScheme(attr_id.0, (prediction_scheme as i32).into())
+            .within_box();
+        DracoError::check(&status, Operation::Encode)?;
+        Ok(self)
+    }
+
+    /// Enables draco's own attribute compression (on by default). When
+    /// disabled, attribute values are stored without prediction or quantization.
+    pub fn set_use_built_in_attribute_compression(mut self, enabled: bool) -> Self {
+        self.encoder
+            .pin_mut()
+            .SetUseBuiltInAttributeCompression(enabled);
+        self
+    }
+
+    pub fn set_encoding_method(mut self, method: impl Into<EncodingMethod>) -> Self {
+        self.encoder
+            .pin_mut()
+            .SetEncodingMethod(method.into().as_raw().into());
+        self
+    }
+
+    /// Selects the connectivity encoding used by [`MeshEncoderMethod::Edgebreaker`]
+    pub fn set_encoding_submethod(mut self, submethod: MeshEdgebreakerMethod) -> Self {
+        self.encoder
+            .pin_mut()
+            .SetEncodingSubmethod((submethod as i32).into());
+        self
+    }
+
+    /// Encode the geometry to an encoder buffer
+    pub fn encode_to_buffer(&mut self) -> DracoResult<EncoderBuffer> {
+        let mut buffer = EncoderBuffer::new();
+
+        let status = unsafe {
+            self.encoder
+                .pin_mut()
+                .EncodeToBuffer(buffer.as_mut_ptr())
+                .within_box()
+        };
+
+        DracoError::check(&status, Operation::Encode)?;
+        Ok(buffer)
+    }
+}
+
+impl GetDracoInner for ExpertEncoder<'_> {
+    type Inner = ffi::draco::ExpertEncoder;
+
+    fn get_inner(&self) -> &UniquePtr<Self::Inner> {
+        &self.encoder
+    }
+
+    fn get_inner_mut(&mut self) -> &mut UniquePtr<Self::Inner> {
+        &mut self.encoder
+    }
+}
+
 pub type EncoderBuffer = WrappedDracoObject<ffi::draco::EncoderBuffer>;
 
 impl EncoderBuffer {
@@ -1,13 +1,117 @@
 #pragma once
 
+#include <iterator>
 #include <memory>
 #include "draco/io/point_cloud_io.h"
 #include "draco/io/mesh_io.h"
+#include "draco/compression/decode.h"
+#include "draco/compression/expert_encode.h"
+#include "draco/attributes/attribute_quantization_transform.h"
+#include "draco/io/file_reader_factory.h"
+#include "draco/io/file_utils.h"
+#include "draco/io/file_writer_factory.h"
+#include "draco/io/gltf_encoder.h"
+#include "draco/io/obj_decoder.h"
+#include "draco/io/obj_encoder.h"
+#include "draco/io/ply_decoder.h"
+#include "draco/io/ply_encoder.h"
+#include "draco/io/stl_decoder.h"
+#include "draco/io/stl_encoder.h"
+#include "draco/scene/scene_utils.h"
+#include "draco/tools/draco_transcoder_lib.h"
 #include <sstream>
 // #include "cxxgen1.h"
 // #include "draco"
 
 
+// Implemented in Rust (see `src/io.rs`), they forward file accesses to the
+// file systems registered with `draco_rs::io::register_file_system`.
+extern "C"
+{
+    void *draco_rs_open_reader(const char *file_name, size_t file_name_size, const uint8_t **data, size_t *size);
+    void draco_rs_close_reader(void *handle);
+    void *draco_rs_open_writer(const char *file_name, size_t file_name_size);
+    bool draco_rs_write(void *handle, const char *data, size_t size);
+    void draco_rs_close_writer(void *handle);
+}
+
+// Kept out of `draco_extra` so that autocxx does not try to bind them.
+namespace draco_rs_io
+{
+    class RustFileReader : public draco::FileReaderInterface
+    {
+    public:
+        static std::unique_ptr<draco::FileReaderInterface> Open(const std::string &file_name)
+        {
+            const uint8_t *data = nullptr;
+            size_t size = 0;
+            void *handle = draco_rs_open_reader(file_name.data(), file_name.size(), &data, &size);
+            if (handle == nullptr)
+            {
+                return nullptr;
+            }
+            return std::unique_ptr<draco::FileReaderInterface>(new RustFileReader(handle, data, size));
+        }
+
+        RustFileReader(const RustFileReader &) = delete;
+        RustFileReader &operator=(const RustFileReader &) = delete;
+
+        ~RustFileReader() override { draco_rs_close_reader(handle_); }
+
+        bool ReadFileToBuffer(std::vector<char> *buffer) override
+        {
+            buffer->assign(data_, data_ + size_);
+            return true;
+        }
+
+        bool ReadFileToBuffer(std::vector<uint8_t> *buffer) override
+        {
+            buffer->assign(data_, data_ + size_);
+            return true;
+        }
+
+        size_t GetFileSize() override { return size_; }
+
+    private:
+        RustFileReader(void *handle, const uint8_t *data, size_t size)
+            : handle_(handle), data_(data), size_(size) {}
+
+        // owns the file content pointed to by `data_`
+        void *handle_;
+        const uint8_t *data_;
+        size_t size_;
+    };
+
+    class RustFileWriter : public draco::FileWriterInterface
+    {
+    public:
+        static std::unique_ptr<draco::FileWriterInterface> Open(const std::string &file_name)
+        {
+            void *handle = draco_rs_open_writer(file_name.data(), file_name.size());
+            if (handle == nullptr)
+            {
+                return nullptr;
+            }
+            return std::unique_ptr<draco::FileWriterInterface>(new RustFileWriter(handle));
+        }
+
+        RustFileWriter(const RustFileWriter &) = delete;
+        RustFileWriter &operator=(const RustFileWriter &) = delete;
+
+        ~RustFileWriter() override { draco_rs_close_writer(handle_); }
+
+        bool Write(const char *buffer, size_t size) override
+        {
+            return draco_rs_write(handle_, buffer, size);
+        }
+
+    private:
+        explicit RustFileWriter(void *handle) : handle_(handle) {}
+
+        void *handle_;
+    };
+} // namespace draco_rs_io
+
 namespace draco_extra
 {
 
@@ -55,6 +159,717 @@ namespace draco_extra
         return std::move(pc).value();
     }
 
+#ifdef DRACO_TRANSCODER_SUPPORTED
+    inline draco::Status unpack_status_or_scene_status(const draco::StatusOr<std::unique_ptr<draco::Scene>> &scene)
+    {
+        return scene.status();
+    }
+
+    inline std::unique_ptr<draco::Scene> unpack_status_or_scene_value(draco::StatusOr<std::unique_ptr<draco::Scene>> &scene)
+    {
+        return std::move(scene).value();
+    }
+
+    inline draco::Status unpack_status_or_transcoder_status(const draco::StatusOr<std::unique_ptr<draco::DracoTranscoder>> &transcoder)
+    {
+        return transcoder.status();
+    }
+
+    inline std::unique_ptr<draco::DracoTranscoder> unpack_status_or_transcoder_value(draco::StatusOr<std::unique_ptr<draco::DracoTranscoder>> &transcoder)
+    {
+        return std::move(transcoder).value();
+    }
+
+    // Creates a transcoder compressing meshes with `geometry`, as the
+    // `DracoTranscodingOptions` holding them cannot be filled from Rust.
+    inline draco::StatusOr<std::unique_ptr<draco::DracoTranscoder>> new_draco_transcoder(const draco::DracoCompressionOptions &geometry)
+    {
+        draco::DracoTranscodingOptions options;
+        options.geometry = geometry;
+        return draco::DracoTranscoder::Create(options);
+    }
+
+    // Transcodes `input_filename` into `output_filename`. Unless empty, the
+    // binary data is written to `output_bin_filename`, and the textures to
+    // `output_resource_directory` (only used with `output_bin_filename`).
+    inline draco::Status draco_transcoder_transcode(draco::DracoTranscoder &transcoder, const std::string &input_filename,
+                                                    const std::string &output_filename, const std::string &output_bin_filename,
+                                                    const std::string &output_resource_directory)
+    {
+        draco::DracoTranscoder::FileOptions file_options;
+        file_options.input_filename = input_filename;
+        file_options.output_filename = output_filename;
+        file_options.output_bin_filename = output_bin_filename;
+        file_options.output_resource_directory = output_resource_directory;
+        return transcoder.Transcode(file_options);
+    }
+
+    // Returns a copy of the mesh `index` of the scene, or nullptr if there is
+    // no such mesh.
+    inline std::unique_ptr<draco::Mesh> scene_copy_mesh(const draco::Scene &scene, int32_t index)
+    {
+        if (index < 0 || index >= scene.NumMeshes())
+        {
+            return nullptr;
+        }
+        std::unique_ptr<draco::Mesh> mesh(new draco::Mesh());
+        mesh->Copy(scene.GetMesh(draco::MeshIndex(index)));
+        return mesh;
+    }
+
+    // Scene graph accessors. Meshes, mesh groups, nodes and materials are
+    // passed around as plain indices, with -1 standing for "none"; the Rust
+    // side checks that they are in range before calling any of these.
+
+    inline int32_t scene_add_mesh(draco::Scene &scene, std::unique_ptr<draco::Mesh> mesh)
+    {
+        return scene.AddMesh(std::move(mesh)).value();
+    }
+
+    inline int32_t scene_add_mesh_group(draco::Scene &scene, const std::string &name)
+    {
+        const draco::MeshGroupIndex index = scene.AddMeshGroup();
+        scene.GetMeshGroup(index)->SetName(name);
+        return index.value();
+    }
+
+    inline std::string scene_mesh_group_name(const draco::Scene &scene, int32_t group)
+    {
+        return scene.GetMeshGroup(draco::MeshGroupIndex(group))->GetName();
+    }
+
+    inline int32_t scene_mesh_group_num_mesh_instances(const draco::Scene &scene, int32_t group)
+    {
+        return scene.GetMeshGroup(draco::MeshGroupIndex(group))->NumMeshInstances();
+    }
+
+    inline void scene_mesh_group_mesh_instance(const draco::Scene &scene, int32_t group, int32_t instance,
+                                               int32_t *mesh_index, int32_t *material_index)
+    {
+        const draco::MeshGroup::MeshInstance &mesh_instance =
+            scene.GetMeshGroup(draco::MeshGroupIndex(group))->GetMeshInstance(instance);
+        *mesh_index = mesh_instance.mesh_index.value();
+        *material_index = mesh_instance.material_index;
+    }
+
+    inline void scene_mesh_group_add_mesh_instance(draco::Scene &scene, int32_t group, int32_t mesh_index, int32_t material_index)
+    {
+        scene.GetMeshGroup(draco::MeshGroupIndex(group))
+            ->AddMeshInstance(draco::MeshGroup::MeshInstance(draco::MeshIndex(mesh_index), material_index));
+    }
+
+    inline int32_t scene_add_node(draco::Scene &scene, const std::string &name)
+    {
+        const draco::SceneNodeIndex index = scene.AddNode();
+        scene.GetNode(index)->SetName(name);
+        return index.value();
+    }
+
+    inline std::string scene_node_name(const draco::Scene &scene, int32_t node)
+    {
+        return scene.GetNode(draco::SceneNodeIndex(node))->GetName();
+    }
+
+    inline int32_t scene_node_mesh_group(const draco::Scene &scene, int32_t node)
+    {
+        const draco::MeshGroupIndex group = scene.GetNode(draco::SceneNodeIndex(node))->GetMeshGroupIndex();
+        return group == draco::kInvalidMeshGroupIndex ? -1 : static_cast<int32_t>(group.value());
+    }
+
+    inline void scene_node_set_mesh_group(draco::Scene &scene, int32_t node, int32_t group)
+    {
+        scene.GetNode(draco::SceneNodeIndex(node))
+            ->SetMeshGroupIndex(group < 0 ? draco::kInvalidMeshGroupIndex : draco::MeshGroupIndex(group));
+    }
+
+    inline int32_t scene_node_num_children(const draco::Scene &scene, int32_t node)
+    {
+        return scene.GetNode(draco::SceneNodeIndex(node))->NumChildren();
+    }
+
+    inline int32_t scene_node_child(const draco::Scene &scene, int32_t node, int32_t i)
+    {
+        return scene.GetNode(draco::SceneNodeIndex(node))->Child(i).value();
+    }
+
+    inline int32_t scene_node_num_parents(const draco::Scene &scene, int32_t node)
+    {
+        return scene.GetNode(draco::SceneNodeIndex(node))->NumParents();
+    }
+
+    inline int32_t scene_node_parent(const draco::Scene &scene, int32_t node, int32_t i)
+    {
+        return scene.GetNode(draco::SceneNodeIndex(node))->Parent(i).value();
+    }
+
+    // Links both ways, as draco keeps the parents of a node next to its
+    // children.
+    inline void scene_add_child(draco::Scene &scene, int32_t parent, int32_t child)
+    {
+        scene.GetNode(draco::SceneNodeIndex(parent))->AddChildIndex(draco::SceneNodeIndex(child));
+        scene.GetNode(draco::SceneNodeIndex(child))->AddParentIndex(draco::SceneNodeIndex(parent));
+    }
+
+    inline int32_t scene_root_node(const draco::Scene &scene, int32_t i)
+    {
+        return scene.GetRootNodeIndex(i).value();
+    }
+
+    inline void scene_add_root_node(draco::Scene &scene, int32_t node)
+    {
+        scene.AddRootNodeIndex(draco::SceneNodeIndex(node));
+    }
+
+    // Reads the transform of a node. `is_set` receives whether the matrix, the
+    // translation, the rotation and the scale are set, in that order; only
+    // the parts that are set are written. The matrix is column-major and the
+    // rotation is a quaternion stored as (x, y, z, w), like in glTF.
+    inline void scene_node_transform(const draco::Scene &scene, int32_t node, bool *is_set, double *matrix,
+                                     double *translation, double *rotation, double *scale)
+    {
+        const draco::TrsMatrix &trs = scene.GetNode(draco::SceneNodeIndex(node))->GetTrsMatrix();
+        is_set[0] = trs.MatrixSet();
+        is_set[1] = trs.TranslationSet();
+        is_set[2] = trs.RotationSet();
+        is_set[3] = trs.ScaleSet();
+        if (trs.MatrixSet())
+        {
+            Eigen::Map<Eigen::Matrix4d>(matrix) = trs.Matrix().value();
+        }
+        if (trs.TranslationSet())
+        {
+            Eigen::Map<Eigen::Vector3d>(translation) = trs.Translation().value();
+        }
+        if (trs.RotationSet())
+        {
+            Eigen::Map<Eigen::Vector4d>(rotation) = trs.Rotation().value().coeffs();
+        }
+        if (trs.ScaleSet())
+        {
+            Eigen::Map<Eigen::Vector3d>(scale) = trs.Scale().value();
+        }
+    }
+
+    // Replaces the transform of a node, see `scene_node_transform()`. Null
+    // pointers leave the corresponding part unset.
+    inline void scene_node_set_transform(draco::Scene &scene, int32_t node, const double *matrix,
+                                         const double *translation, const double *rotation, const double *scale)
+    {
+        draco::TrsMatrix trs;
+        if (matrix)
+        {
+            trs.SetMatrix(Eigen::Map<const Eigen::Matrix4d>(matrix));
+        }
+        if (translation)
+        {
+            trs.SetTranslation(Eigen::Map<const Eigen::Vector3d>(translation));
+        }
+        if (rotation)
+        {
+            trs.SetRotation(Eigen::Quaterniond(Eigen::Map<const Eigen::Vector4d>(rotation)));
+        }
+        if (scale)
+        {
+            trs.SetScale(Eigen::Map<const Eigen::Vector3d>(scale));
+        }
+        scene.GetNode(draco::SceneNodeIndex(node))->SetTrsMatrix(trs);
+    }
+
+    // Writes the local transformation matrix of a node, column-major.
+    inline void scene_node_transformation_matrix(const draco::Scene &scene, int32_t node, double *matrix)
+    {
+        Eigen::Map<Eigen::Matrix4d>(matrix) =
+            scene.GetNode(draco::SceneNodeIndex(node))->GetTrsMatrix().ComputeTransformationMatrix();
+    }
+
+    inline int32_t scene_num_materials(const draco::Scene &scene)
+    {
+        return scene.GetMaterialLibrary().NumMaterials();
+    }
+
+    // Appends a material with default properties.
+    inline int32_t scene_add_material(draco::Scene &scene, const std::string &name)
+    {
+        draco::MaterialLibrary &library = scene.GetMaterialLibrary();
+        const int32_t index = library.NumMaterials();
+        library.MutableMaterial(index)->SetName(name);
+        return index;
+    }
+
+    inline std::string scene_material_name(const draco::Scene &scene, int32_t index)
+    {
+        return scene.GetMaterialLibrary().GetMaterial(index)->GetName();
+    }
+
+    // Builds the options used to compress meshes written to glTF. Positions
+    // are snapped to a grid when `position_grid_spacing` is positive, and
+    // quantized with `position_quantization_bits` otherwise.
+    inline std::unique_ptr<draco::DracoCompressionOptions> new_draco_compression_options(
+        int32_t compression_level, int32_t position_quantization_bits, float position_grid_spacing,
+        int32_t quantization_bits_normal, int32_t quantization_bits_tex_coord, int32_t quantization_bits_color,
+        int32_t quantization_bits_generic, int32_t quantization_bits_tangent, int32_t quantization_bits_weight,
+        bool find_non_degenerate_texture_quantization)
+    {
+        std::unique_ptr<draco::DracoCompressionOptions> options(new draco::DracoCompressionOptions());
+        options->compression_level = compression_level;
+        if (position_grid_spacing > 0.f)
+        {
+            options->quantization_position.SetGrid(position_grid_spacing);
+        }
+        else
+        {
+            options->quantization_position.SetQuantizationBits(position_quantization_bits);
+        }
+        options->quantization_bits_normal = quantization_bits_normal;
+        options->quantization_bits_tex_coord = quantization_bits_tex_coord;
+        options->quantization_bits_color = quantization_bits_color;
+        options->quantization_bits_generic = quantization_bits_generic;
+        options->quantization_bits_tangent = quantization_bits_tangent;
+        options->quantization_bits_weight = quantization_bits_weight;
+        options->find_non_degenerate_texture_quantization = find_non_degenerate_texture_quantization;
+        return options;
+    }
+
+    // Enables compression with `options` for all meshes of the scene, or
+    // disables it when `options` is null.
+    inline void scene_set_compression_options(draco::Scene &scene, const draco::DracoCompressionOptions *options)
+    {
+        draco::SceneUtils::SetDracoCompressionOptions(options, &scene);
+    }
+
+    // Writes the geometry as glTF, or as GLB when the file name ends with
+    // ".glb". Other resources (.bin, textures) are written next to the file.
+    inline draco::Status gltf_encode_mesh_to_file(const draco::Mesh &mesh, const std::string &file_name, bool verbose, const std::string &copyright)
+    {
+        draco::GltfEncoder encoder;
+        encoder.set_output_type(verbose ? draco::GltfEncoder::VERBOSE : draco::GltfEncoder::COMPACT);
+        encoder.set_copyright(copyright);
+        return encoder.EncodeFile(mesh, file_name);
+    }
+
+    inline draco::Status gltf_encode_scene_to_file(const draco::Scene &scene, const std::string &file_name, bool verbose, const std::string &copyright)
+    {
+        draco::GltfEncoder encoder;
+        encoder.set_output_type(verbose ? draco::GltfEncoder::VERBOSE : draco::GltfEncoder::COMPACT);
+        encoder.set_copyright(copyright);
+        return encoder.EncodeFile(scene, file_name);
+    }
+
+    // Encodes the geometry as GLB into `out_buffer`.
+    inline draco::Status gltf_encode_mesh_to_buffer(const draco::Mesh &mesh, bool verbose, const std::string &copyright, draco::EncoderBuffer *out_buffer)
+    {
+        draco::GltfEncoder encoder;
+        encoder.set_output_type(verbose ? draco::GltfEncoder::VERBOSE : draco::GltfEncoder::COMPACT);
+        encoder.set_copyright(copyright);
+        return encoder.EncodeToBuffer(mesh, out_buffer);
+    }
+
+    inline draco::Status gltf_encode_scene_to_buffer(const draco::Scene &scene, bool verbose, const std::string &copyright, draco::EncoderBuffer *out_buffer)
+    {
+        draco::GltfEncoder encoder;
+        encoder.set_output_type(verbose ? draco::GltfEncoder::VERBOSE : draco::GltfEncoder::COMPACT);
+        encoder.set_copyright(copyright);
+        return encoder.EncodeToBuffer(scene, out_buffer);
+    }
+#endif
+
+    // Unpacks `Decoder::GetEncodedGeometryType()` into `out_type`, as the
+    // `StatusOr<EncodedGeometryType>` it returns is opaque to Rust. The buffer
+    // position is left untouched.
+    inline draco::Status decoder_get_encoded_geometry_type(draco::DecoderBuffer *in_buffer, int32_t *out_type)
+    {
+        auto status_or = draco::Decoder::GetEncodedGeometryType(in_buffer);
+        if (!status_or.ok())
+        {
+            return status_or.status();
+        }
+        *out_type = static_cast<int32_t>(status_or.value());
+        return draco::OkStatus();
+    }
+
+    // The following methods are defined on the templated `EncoderBase`, which
+    // is blocked from the bindings.
+    inline void encoder_set_track_encoded_properties(draco::Encoder &encoder, bool flag)
+    {
+        encoder.SetTrackEncodedProperties(flag);
+    }
+
+    inline size_t encoder_num_encoded_points(const draco::Encoder &encoder)
+    {
+        return encoder.num_encoded_points();
+    }
+
+    inline size_t encoder_num_encoded_faces(const draco::Encoder &encoder)
+    {
+        return encoder.num_encoded_faces();
+    }
+
+    // `ExpertEncoder` has one constructor overload per geometry type, which
+    // autocxx cannot tell apart, so we provide one factory per type.
+    inline std::unique_ptr<draco::ExpertEncoder> new_expert_encoder_for_point_cloud(const draco::PointCloud &pc)
+    {
+        return std::unique_ptr<draco::ExpertEncoder>(new draco::ExpertEncoder(pc));
+    }
+
+    inline std::unique_ptr<draco::ExpertEncoder> new_expert_encoder_for_mesh(const draco::Mesh &mesh)
+    {
+        return std::unique_ptr<draco::ExpertEncoder>(new draco::ExpertEncoder(mesh));
+    }
+
+    // autocxx does not expose the methods of a base class on the derived class,
+    // so we hand out the `PointCloud` part of a mesh explicitly.
+    inline const draco::PointCloud &mesh_as_point_cloud(const draco::Mesh &mesh)
+    {
+        return mesh;
+    }
+
+    inline draco::PointCloud &mesh_as_point_cloud_mut(draco::Mesh &mesh)
+    {
+        return mesh;
+    }
+
+    inline const draco::GeometryAttribute &point_attribute_as_geometry_attribute(const draco::PointAttribute &attribute)
+    {
+        return attribute;
+    }
+
+    // Bounds-checked version of `PointCloud::attribute()`, which only
+    // DCHECKs its argument. Returns nullptr for unknown attribute ids.
+    inline const draco::PointAttribute *point_cloud_attribute(const draco::PointCloud &pc, int32_t att_id)
+    {
+        if (att_id < 0 || att_id >= pc.num_attributes())
+        {
+            return nullptr;
+        }
+        return pc.attribute(att_id);
+    }
+
+    // Copies the values of the first `num_points` points into `out_data`, which
+    // must have room for `num_points * byte_stride` bytes. This avoids one FFI
+    // call per point when reading a whole attribute. Returns the index of the
+    // first point mapped to an invalid attribute value, or -1 on success.
+    inline int64_t point_attribute_copy_mapped_values(const draco::PointAttribute &attribute, uint32_t num_points, uint8_t *out_data)
+    {
+        const int64_t stride = attribute.byte_stride();
+        for (draco::PointIndex i(0); i < num_points; ++i)
+        {
+            const draco::AttributeValueIndex avi = attribute.mapped_index(i);
+            if (avi.value() >= attribute.size())
+            {
+                return i.value();
+            }
+            attribute.GetValue(avi, out_data + stride * i.value());
+        }
+        return -1;
+    }
+
+    // Returns the address of the first attribute value inside the attribute
+    // buffer, or nullptr if the buffer does not hold all `size()` values.
+    inline const uint8_t *point_attribute_values_data(const draco::PointAttribute &attribute)
+    {
+        const draco::DataBuffer *buffer = attribute.buffer();
+        if (buffer == nullptr || attribute.size() == 0)
+        {
+            return nullptr;
+        }
+        const size_t required = attribute.byte_offset() + attribute.size() * attribute.byte_stride();
+        if (buffer->data_size() < required)
+        {
+            return nullptr;
+        }
+        return buffer->data() + attribute.byte_offset();
+    }
+
+    // Reads the quantization parameters stored in the transform data of an
+    // attribute decoded without dequantization. `out_min_values` must have
+    // room for `num_components` floats. Returns false if the attribute does
+    // not carry a quantization transform.
+    inline bool point_attribute_quantization_parameters(const draco::PointAttribute &attribute, int32_t *out_bits, float *out_range, float *out_min_values)
+    {
+        draco::AttributeQuantizationTransform transform;
+        if (!transform.InitFromAttribute(attribute))
+        {
+            return false;
+        }
+        *out_bits = transform.quantization_bits();
+        *out_range = transform.range();
+        for (int i = 0; i < attribute.num_components(); ++i)
+        {
+            out_min_values[i] = transform.min_value(i);
+        }
+        return true;
+    }
+
+    // Adds an identity-mapped attribute holding one value per point, copied
+    // from `values` (which must hold `num_points * byte_stride` bytes).
+    // Returns the id of the new attribute, or -1 if the point cloud has no
+    // points (draco then leaves the attribute without a buffer) or if the
+    // attribute type is invalid.
+    inline int32_t point_cloud_add_identity_attribute(draco::PointCloud &pc, draco::GeometryAttribute::Type attribute_type, int8_t num_components, draco::DataType data_type, const uint8_t *values)
+    {
+        if (pc.num_points() == 0 || attribute_type == draco::GeometryAttribute::INVALID)
+        {
+            return -1;
+        }
+        draco::GeometryAttribute va;
+        va.Init(attribute_type, nullptr, num_components, data_type, false,
+                draco::DataTypeLength(data_type) * num_components, 0);
+        const int32_t att_id = pc.AddAttribute(va, true, pc.num_points());
+        if (att_id < 0)
+        {
+            return -1;
+        }
+        draco::PointAttribute *const att = pc.attribute(att_id);
+        att->buffer()->Write(0, values, pc.num_points() * att->byte_stride());
+        return att_id;
+    }
+
+    // `Mesh::Face` is a `std::array<PointIndex, 3>` which autocxx cannot bind,
+    // so faces are accessed one corner at a time.
+    inline uint32_t mesh_face_point(const draco::Mesh &mesh, uint32_t face_id, int32_t corner)
+    {
+        return mesh.face(draco::FaceIndex(face_id))[corner].value();
+    }
+
+    // Replaces the faces of the mesh by `num_faces` faces read from
+    // `indices`, three point indices per face.
+    inline void mesh_set_faces(draco::Mesh &mesh, const uint32_t *indices, uint32_t num_faces)
+    {
+        mesh.SetNumFaces(num_faces);
+        for (uint32_t i = 0; i < num_faces; ++i)
+        {
+            mesh.SetFace(draco::FaceIndex(i),
+                         {{draco::PointIndex(indices[3 * i]),
+                           draco::PointIndex(indices[3 * i + 1]),
+                           draco::PointIndex(indices[3 * i + 2])}});
+        }
+    }
+
+    // Encodes the point cloud into `out_buffer` using one of the file format
+    // encoders. `format` is 0 for PLY and 1 for OBJ (see `io::Format`).
+    inline draco::Status point_cloud_encode_to_format(const draco::PointCloud &pc, int32_t format, draco::EncoderBuffer *out_buffer)
+    {
+        switch (format)
+        {
+        case 0:
+        {
+            draco::PlyEncoder encoder;
+            if (!encoder.EncodeToBuffer(pc, out_buffer))
+            {
+                return draco::Status(draco::Status::DRACO_ERROR, "Failed to encode PLY data.");
+            }
+            return draco::OkStatus();
+        }
+        case 1:
+        {
+            draco::ObjEncoder encoder;
+            if (!encoder.EncodeToBuffer(pc, out_buffer))
+            {
+                return draco::Status(draco::Status::DRACO_ERROR, "Failed to encode OBJ data.");
+            }
+            return draco::OkStatus();
+        }
+        default:
+            return draco::Status(draco::Status::UNSUPPORTED_FEATURE, "Point clouds can only be written as PLY, OBJ or DRC.");
+        }
+    }
+
+    // Same as `point_cloud_encode_to_format`, keeping the faces of the mesh.
+    // `format` can also be 2 for STL.
+    inline draco::Status mesh_encode_to_format(const draco::Mesh &mesh, int32_t format, draco::EncoderBuffer *out_buffer)
+    {
+        switch (format)
+        {
+        case 0:
+        {
+            draco::PlyEncoder encoder;
+            if (!encoder.EncodeToBuffer(mesh, out_buffer))
+            {
+                return draco::Status(draco::Status::DRACO_ERROR, "Failed to encode PLY data.");
+            }
+            return draco::OkStatus();
+        }
+        case 1:
+        {
+            draco::ObjEncoder encoder;
+            if (!encoder.EncodeToBuffer(mesh, out_buffer))
+            {
+                return draco::Status(draco::Status::DRACO_ERROR, "Failed to encode OBJ data.");
+            }
+            return draco::OkStatus();
+        }
+        case 2:
+        {
+            draco::StlEncoder encoder;
+            return encoder.EncodeToBuffer(mesh, out_buffer);
+        }
+        default:
+            return draco::Status(draco::Status::UNSUPPORTED_FEATURE, "Unknown mesh file format.");
+        }
+    }
+
+    // Decodes PLY (0) or OBJ (1) data held by `buffer` into `out_pc` (see
+    // `io::Format`).
+    inline draco::Status point_cloud_decode_from_format(draco::DecoderBuffer *buffer, int32_t format, draco::PointCloud &out_pc)
+    {
+        switch (format)
+        {
+        case 0:
+        {
+            draco::PlyDecoder decoder;
+            return decoder.DecodeFromBuffer(buffer, &out_pc);
+        }
+        case 1:
+        {
+            draco::ObjDecoder decoder;
+            return decoder.DecodeFromBuffer(buffer, &out_pc);
+        }
+        default:
+            return draco::Status(draco::Status::UNSUPPORTED_FEATURE, "Point clouds can only be read from PLY, OBJ or DRC data.");
+        }
+    }
+
+    // Same as `point_cloud_decode_from_format`, keeping the faces. `format`
+    // can also be 2 for STL. The OBJ options are ignored for other formats.
+    inline draco::Status mesh_decode_from_format(draco::DecoderBuffer *buffer, int32_t format, bool use_metadata, bool preserve_polygons, draco::Mesh &out_mesh)
+    {
+        switch (format)
+        {
+        case 0:
+        {
+            draco::PlyDecoder decoder;
+            return decoder.DecodeFromBuffer(buffer, &out_mesh);
+        }
+        case 1:
+        {
+            draco::ObjDecoder decoder;
+            decoder.set_use_metadata(use_metadata);
+            decoder.set_preserve_polygons(preserve_polygons);
+            return decoder.DecodeFromBuffer(buffer, &out_mesh);
+        }
+        case 2:
+        {
+            draco::StlDecoder decoder;
+            draco::StatusOr<std::unique_ptr<draco::Mesh>> mesh = decoder.DecodeFromBuffer(buffer);
+            if (!mesh.ok())
+            {
+                return mesh.status();
+            }
+            out_mesh.Copy(*mesh.value());
+            return draco::OkStatus();
+        }
+        default:
+            return draco::Status(draco::Status::UNSUPPORTED_FEATURE, "Unknown mesh file format.");
+        }
+    }
+
+    // Registers the reader and writer backed by the Rust file systems with
+    // draco. draco consults them in registration order, so they only get
+    // the files that the stdio implementations, registered at startup, cannot
+    // open.
+    inline bool register_rust_file_system()
+    {
+        return draco::FileReaderFactory::RegisterReader(draco_rs_io::RustFileReader::Open) &&
+               draco::FileWriterFactory::RegisterWriter(draco_rs_io::RustFileWriter::Open);
+    }
+
+    // Writes `size` bytes to a file through draco's file writers, so that
+    // paths the disk cannot serve reach the Rust file systems.
+    inline bool write_buffer_to_file(const std::string &file_name, const uint8_t *data, size_t size)
+    {
+        return draco::WriteBufferToFile(data, size, file_name);
+    }
+
+    // Sets the entry `name` of the metadata to the raw bytes of `data`. draco
+    // stores every entry type as plain bytes, so this covers all of them.
+    inline void metadata_add_entry_bytes(draco::Metadata &metadata, const std::string &name, const uint8_t *data, size_t size)
+    {
+        metadata.AddEntryBinary(name, std::vector<uint8_t>(data, data + size));
+    }
+
+    inline bool metadata_add_sub_metadata(draco::Metadata &metadata, const std::string &name, std::unique_ptr<draco::Metadata> sub_metadata)
+    {
+        return metadata.AddSubMetadata(name, std::move(sub_metadata));
+    }
+
+    // Entries and sub-metadata are stored in ordered maps; the accessors below
+    // look them up by their position in the map.
+    inline std::string metadata_entry_name(const draco::Metadata &metadata, int32_t index)
+    {
+        return std::next(metadata.entries().begin(), index)->first;
+    }
+
+    inline const uint8_t *metadata_entry_data(const draco::Metadata &metadata, int32_t index, size_t *size)
+    {
+        const std::vector<uint8_t> &data = std::next(metadata.entries().begin(), index)->second.data();
+        *size = data.size();
+        return data.data();
+    }
+
+    inline int32_t metadata_num_sub_metadatas(const draco::Metadata &metadata)
+    {
+        return static_cast<int32_t>(metadata.sub_metadatas().size());
+    }
+
+    inline std::string metadata_sub_metadata_name(const draco::Metadata &metadata, int32_t index)
+    {
+        return std::next(metadata.sub_metadatas().begin(), index)->first;
+    }
+
+    inline const draco::Metadata *metadata_sub_metadata(const draco::Metadata &metadata, int32_t index)
+    {
+        return std::next(metadata.sub_metadatas().begin(), index)->second.get();
+    }
+
+    // Returns the geometry metadata of the point cloud, or nullptr if it has none.
+    inline const draco::Metadata *point_cloud_metadata(const draco::PointCloud &pc)
+    {
+        return pc.GetMetadata();
+    }
+
+    // Replaces the geometry-level entries of the point cloud metadata, keeping
+    // the metadata already attached to its attributes.
+    inline void point_cloud_set_metadata(draco::PointCloud &pc, const draco::Metadata &metadata)
+    {
+        std::unique_ptr<draco::GeometryMetadata> geometry_metadata(new draco::GeometryMetadata(metadata));
+        if (pc.GetMetadata() != nullptr)
+        {
+            for (const auto &att_metadata : pc.GetMetadata()->attribute_metadatas())
+            {
+                geometry_metadata->AddAttributeMetadata(
+                    std::unique_ptr<draco::AttributeMetadata>(new draco::AttributeMetadata(*att_metadata)));
+            }
+        }
+        pc.AddMetadata(std::move(geometry_metadata));
+    }
+
+    // Returns the metadata of the attribute `att_id`, or nullptr if the
+    // attribute does not exist or has no metadata.
+    inline const draco::Metadata *point_cloud_attribute_metadata(const draco::PointCloud &pc, int32_t att_id)
+    {
+        if (att_id < 0 || att_id >= pc.num_attributes())
+        {
+            return nullptr;
+        }
+        return pc.GetAttributeMetadataByAttributeId(att_id);
+    }
+
+    // Replaces the metadata of the attribute `att_id`. Returns false if the
+    // attribute does not exist.
+    inline bool point_cloud_set_attribute_metadata(draco::PointCloud &pc, int32_t att_id, const draco::Metadata &metadata)
+    {
+        if (att_id < 0 || att_id >= pc.num_attributes())
+        {
+            return false;
+        }
+        if (pc.metadata() != nullptr)
+        {
+            pc.metadata()->DeleteAttributeMetadataByUniqueId(pc.attribute(att_id)->unique_id());
+        }
+        pc.AddAttributeMetadata(att_id, std::unique_ptr<draco::AttributeMetadata>(new draco::AttributeMetadata(metadata)));
+        return true;
+    }
+
     // inline std::unique_ptr<MeshWithStatus> unpack_status_mesh(draco::StatusOr<std::unique_ptr<draco::Mesh>> &pc)
     // {
 
@@ -0,0 +1,308 @@
+use crate::{
+    attribute::is_named_attribute_type,
+    mesh::Mesh,
+    metadata::{Metadata, ATTRIBUTE_NAME_ENTRY},
+    pointcloud::PointCloud,
+    prelude::*,
+};
+use std::pin::Pin;
+
+/// Access to the point cloud part of a draco geometry.
+///
+/// In draco, `Mesh` is a subclass of `PointCloud`, so everything related to
+/// points and attributes is shared between the two. Wrapped geometries only
+/// need to provide a reference to their underlying `PointCloud` and get all
+/// the attribute accessors for free.
+pub trait AsPointCloud {
+    /// Returns the underlying draco point cloud
+    fn as_point_cloud(&self) -> &ffi::draco::PointCloud;
+
+    /// Returns the underlying draco point cloud, mutably
+    fn as_point_cloud_mut(&mut self) -> Pin<&mut ffi::draco::PointCloud>;
+
+    fn num_points(&self) -> u32 {
+        self.as_point_cloud().num_points()
+    }
+
+    // Returns the number of attributes (of any type) stored in the geometry.
+    fn num_attributes(&self) -> i32 {
+        self.as_point_cloud().num_attributes()
+    }
+
+    // Returns the number of named attributes of a given type.
+    fn num_named_attributes(&self, attr_type: ffi::draco::GeometryAttribute_Type) -> i32 {
+        self.as_point_cloud().NumNamedAttributes(attr_type)
+    }
+
+    // Returns the id of the i-th named attribute of a given type.
+    fn get_named_attribute_id(
+        &self,
+        attr_type: ffi::draco::GeometryAttribute_Type,
+        i: usize,
+    ) -> Option<AttrId> {
+        // draco does not check the lower bound of `i`
+        if !is_named_attribute_type(attr_type) {
+            return None;
+        }
+        let i = i32::try_from(i).ok()?;
+        let id = self
+            .as_point_cloud()
+            .GetNamedAttributeId1(attr_type, i.into());
+        if id < 0 {
+            None
+        } else {
+            Some(AttrId(id))
+        }
+    }
+
+    // Returns the attribute with the given id, or `None` if there is no such attribute.
+    fn attribute(&self, attr_id: AttrId) -> Option<PointAttributeRef<'_>> {
+        unsafe {
+            PointAttributeRef::from_ptr(ffi::draco_extra::point_cloud_attribute(
+                self.as_point_cloud(),
+                attr_id.0,
+            ))
+        }
+    }
+
+    /// Returns the id of the first attribute whose metadata has a `"name"`
+    /// entry equal to `name`.
+    ///
+    /// Unlike attribute ids, which encoders are free to reorder, names
+    /// survive a round trip through the encoded data. Names are attached by
+    /// [`PointCloudBuilder::add_named_attribute`](crate::pointcloud::PointCloudBuilder::add_named_attribute)
+    /// or by [`Self::set_attribute_name`].
+    fn attribute_id_by_name(&self, name: &str) -> Option<AttrId> {
+        let id = self
+            .as_point_cloud()
+            .GetAttributeIdByMetadataEntry(ATTRIBUTE_NAME_ENTRY, name);
+        if id.0 < 0 {
+            None
+        } else {
+            Some(id.into())
+        }
+    }
+
+    /// Returns the attribute with the given name, see [`Self::attribute_id_by_name`].
+    fn attribute_by_name(&self, name: &str) -> Option<PointAttributeRef<'_>> {
+        self.attribute(self.attribute_id_by_name(name)?)
+    }
+
+    /// Stores `name` in the `"name"` entry of the attribute metadata, keeping
+    /// its other entries.
+    fn set_attribute_name(
+        &mut self,
+        attr_id: impl Into<AttrId>,
+        name: &str,
+    ) -> Result<(), AttributeError> {
+        let attr_id = attr_id.into();
+        let mut metadata = self.attribute_metadata(attr_id).unwrap_or_default();
+        metadata.add_string(ATTRIBUTE_NAME_ENTRY, name);
+        self.add_attribute_metadata(attr_id, &metadata)
+    }
+
+    // Returns the attribute with the given unique id.
+    fn attribute_by_unique_id(&self, unique_id: u32) -> Option<PointAttributeRef<'_>> {
+        unsafe {
+            PointAttributeRef::from_ptr(self.as_point_cloud().GetAttributeByUniqueId(unique_id))
+        }
+    }
+
+    // Returns the i-th named attribute of a given type.
+    fn named_attribute(
+        &self,
+        attr_type: ffi::draco::GeometryAttribute_Type,
+        i: usize,
+    ) -> Option<PointAttributeRef<'_>> {
+        self.attribute(self.get_named_attribute_id(attr_type, i)?)
+    }
+
+    // Returns the named attribute of a given type and unique id.
+    fn named_attribute_by_unique_id(
+        &self,
+        attr_type: ffi::draco::GeometryAttribute_Type,
+        unique_id: u32,
+    ) -> Option<PointAttributeRef<'_>> {
+        // draco indexes its per-type tables with `attr_type` unchecked
+        if !is_named_attribute_type(attr_type) {
+            return None;
+        }
+        unsafe {
+            PointAttributeRef::from_ptr(
+                self.as_point_cloud()
+                    .GetNamedAttributeByUniqueId(attr_type, unique_id),
+            )
+        }
+    }
+
+    /// Returns the value of the attribute at the given point.
+    ///
+    /// Fails if the attribute does not exist, its layout does not match
+    /// `T` and `N`, or the point is out of range.
+    fn get_point<T: DracoComponent, const N: usize>(
+        &self,
+        attr_id: TypedAttrId<T, N>,
+        point_index: impl Into<ffi::draco::PointIndex>,
+    ) -> Result<[T; N], AttributeError> {
+        self.attribute(attr_id.id())
+            .ok_or(AttributeError::NotFound(attr_id.id()))?
+            .get_value(point_index)
+    }
+
+    /// Returns the values of the attribute for all points, in point order.
+    fn get_points<T: DracoComponent, const N: usize>(
+        &self,
+        attr_id: TypedAttrId<T, N>,
+    ) -> Result<Vec<[T; N]>, AttributeError> {
+        self.attribute(attr_id.id())
+            .ok_or(AttributeError::NotFound(attr_id.id()))?
+            .get_values()
+    }
+
+    /// Borrows the values of an identity-mapped attribute without copying.
+    ///
+    /// See [`PointAttributeRef::as_slice`] for when this is possible.
+    fn get_points_slice<T: DracoComponent, const N: usize>(
+        &self,
+        attr_id: TypedAttrId<T, N>,
+    ) -> Result<&[T], AttributeError> {
+        let attr = self
+            .attribute(attr_id.id())
+            .ok_or(AttributeError::NotFound(attr_id.id()))?;
+        attr.check_layout::<T>(N)?;
+        attr.as_slice()
+    }
+
+    /// Adds an identity-mapped attribute with one value per point.
+    ///
+    /// `values` must contain exactly [`Self::num_points`] entries. Fails with
+    /// [`AttributeError::NotAdded`] if the geometry has no points or if the
+    /// attribute type is `INVALID`.
+    fn add_attribute<T: DracoComponent, const N: usize>(
+        &mut self,
+        attribute_type: ffi::draco::GeometryAttribute_Type,
+        values: &[[T; N]],
+    ) -> Result<TypedAttrId<T, N>, AttributeError> {
+        if values.len() != self.num_points() as usize {
+            return Err(AttributeError::ValueCountMismatch {
+                expected: self.num_points() as usize,
+                actual: values.len(),
+            });
+        }
+        let attr_id = unsafe {
+            ffi::draco_extra::point_cloud_add_identity_attribute(
+                self.as_point_cloud_mut(),
+                attribute_type,
+                TypedAttrId::<T, N>::NUM_COMPONENTS,
+                T::DATA_TYPE,
+                values.as_ptr() as *const u8,
+            )
+        };
+        if attr_id < 0 {
+            return Err(AttributeError::NotAdded(attribute_type));
+        }
+        Ok(TypedAttrId::new(AttrId(attr_id)))
+    }
+
+    /// Returns a copy of the geometry-level metadata, or `None` if the
+    /// geometry has no metadata.
+    fn metadata(&self) -> Option<Metadata> {
+        let metadata =
+            unsafe { ffi::draco_extra::point_cloud_metadata(self.as_point_cloud()).as_ref() };
+        metadata.map(Metadata::from_draco)
+    }
+
+    /// Replaces the geometry-level metadata. The metadata of the attributes
+    /// is kept. Metadata is stored in the encoded data by the encoders.
+    fn add_metadata(&mut self, metadata: &Metadata) {
+        ffi::draco_extra::point_cloud_set_metadata(self.as_point_cloud_mut(), &metadata.to_draco());
+    }
+
+    /// Returns a copy of the metadata attached to an attribute, or `None` if
+    /// the attribute does not exist or has no metadata.
+    fn attribute_metadata(&self, attr_id: impl Into<AttrId>) -> Option<Metadata> {
+        let metadata = unsafe {
+            ffi::draco_extra::point_cloud_attribute_metadata(
+                self.as_point_cloud(),
+                attr_id.into().0,
+            )
+            .as_ref()
+        };
+        metadata.map(Metadata::from_draco)
+    }
+
+    /// Replaces the metadata attached to an attribute.
+    fn add_attribute_metadata(
+        &mut self,
+        attr_id: impl Into<AttrId>,
+        metadata: &Metadata,
+    ) -> Result<(), AttributeError> {
+        let attr_id = attr_id.into();
+        if ffi::draco_extra::point_cloud_set_attribute_metadata(
+            self.as_point_cloud_mut(),
+            attr_id.0,
+            &metadata.to_draco(),
+        ) {
+            Ok(())
+        } else {
+            Err(AttributeError::NotFound(attr_id))
+        }
+    }
+}
+
+/// A decoded geometry whose kind is only known at runtime, see
+/// [`Decoder::decode`](crate::decode::Decoder::decode).
+pub enum Geometry {
+    PointCloud(PointCloud),
+    Mesh(Mesh),
+}
+
+impl Geometry {
+    pub fn is_mesh(&self) -> bool {
+        matches!(self, Self::Mesh(_))
+    }
+
+    pub fn as_mesh(&self) -> Option<&Mesh> {
+        match self {
+            Self::Mesh(mesh) => Some(mesh),
+            Self::PointCloud(_) => None,
+        }
+    }
+
+    pub fn into_mesh(self) -> Option<Mesh> {
+        match self {
+            Self::Mesh(mesh) => Some(mesh),
+            Self::PointCloud(_) => None,
+        }
+    }
+
+    pub fn as_pointcloud(&self) -> Option<&PointCloud> {
+        match self {
+            Self::PointCloud(pc) => Some(pc),
+            Self::Mesh(_) => None,
+        }
+    }
+
+    pub fn into_pointcloud(self) -> Option<PointCloud> {
+        match self {
+            Self::PointCloud(pc) => Some(pc),
+            Self::Mesh(_) => None,
+        }
+    }
+}
+
+impl AsPointCloud for Geometry {
+    fn as_point_cloud(&self) -> &ffi::draco::PointCloud {
+        match self {
+            Self::PointCloud(pc) => pc.as_point_cloud(),
+            Self::Mesh(mesh) => mesh.as_point_cloud(),
+        }
+    }
+
+    fn as_point_cloud_mut(&mut self) -> Pin<&mut ffi::draco::PointCloud> {
+        match self {
+            Self::PointCloud(pc) => pc.as_point_cloud_mut(),
+            Self::Mesh(mesh) => mesh.as_point_cloud_mut(),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::{encode::EncoderBuffer, io::path_to_str, mesh::Mesh, prelude::*, scene::Scene};
+use autocxx::prelude::*;
+use std::path::Path;
+
+/// How position values are quantized when a mesh is compressed.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum PositionQuantization {
+    /// Quantizes positions with the given number of bits, within the bounds
+    /// of each mesh.
+    Bits(i32),
+    /// Snaps positions to a grid with the given spacing, shared by all meshes.
+    Grid(f32),
+}
+
+/// Settings of the `KHR_draco_mesh_compression` extension used when writing
+/// compressed meshes to glTF, see [`Mesh::set_compression_options`].
+///
+/// The defaults are the ones of draco.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DracoCompressionOptions {
+    /// From 0 (fastest) to 10 (smallest output)
+    pub compression_level: i32,
+    pub position_quantization: PositionQuantization,
+    pub quantization_bits_normal: i32,
+    pub quantization_bits_tex_coord: i32,
+    pub quantization_bits_color: i32,
+    pub quantization_bits_generic: i32,
+    pub quantization_bits_tangent: i32,
+    pub quantization_bits_weight: i32,
+    /// Searches for a number of texture coordinate bits that does not
+    /// collapse any triangle in texture space.
+    pub find_non_degenerate_texture_quantization: bool,
+}
+
+impl Default for DracoCompressionOptions {
+    fn default() -> Self {
+        Self {
+            compression_level: 7,
+            position_quantization: PositionQuantization::Bits(11),
+            quantization_bits_normal: 8,
+            quantization_bits_tex_coord: 10,
+            quantization_bits_color: 8,
+            quantization_bits_generic: 8,
+            quantization_bits_tangent: 8,
+            quantization_bits_weight: 8,
+            find_non_degenerate_texture_quantization: false,
+        }
+    }
+}
+
+impl DracoCompressionOptions {
+    // Builds the equivalent draco options, checking that all values are in range.
+    pub(crate) fn to_draco(&self) -> DracoResult<UniquePtr<ffi::draco::DracoCompressionOptions>> {
+        let (position_bits, grid_spacing) = match self.position_quantization {
+            PositionQuantization::Bits(bits) => (bits, 0.0),
+            PositionQuantization::Grid(spacing) if spacing > 0.0 => (0, spacing),
+            PositionQuantization::Grid(spacing) => {
+                return Err(DracoError::InvalidParameter {
+                    operation: Operation::Encode,
+                    message: format!("Invalid position quantization spacing {}.", spacing),
+                })
+            }
+        };
+        let options = ffi::draco_extra::new_draco_compression_options(
+            self.compression_level,
+            position_bits,
+            grid_spacing,
+            self.quantization_bits_normal,
+            self.quantization_bits_tex_coord,
+            self.quantization_bits_color,
+            self.quantization_bits_generic,
+            self.quantization_bits_tangent,
+            self.quantization_bits_weight,
+            self.find_non_degenerate_texture_quantization,
+        );
+        DracoError::check(&options.Check().within_box(), Operation::Encode)?;
+        Ok(options)
+    }
+}
+
+/// Layout of the JSON part of the glTF output.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum GltfOutputType {
+    /// Only required and non-default data
+    #[default]
+    Compact,
+    /// Also writes default values, with readable JSON
+    Verbose,
+}
+
+mod sealed {
+    use crate::prelude::ffi;
+    use autocxx::prelude::*;
+
+    pub trait Sealed {
+        fn encode_to_file(
+            &self,
+            file_name: &str,
+            verbose: bool,
+            copyright: &str,
+        ) -> impl New<Output = ffi::draco::Status>;
+
+        unsafe fn encode_to_buffer(
+            &self,
+            verbose: bool,
+            copyright: &str,
+            out_buffer: *mut ffi::draco::EncoderBuffer,
+        ) -> impl New<Output = ffi::draco::Status>;
+    }
+}
+
+/// Geometry that can be written as glTF: [`Mesh`] and [`Scene`].
+pub trait GltfGeometry: sealed::Sealed {}
+
+impl sealed::Sealed for Mesh {
+    fn encode_to_file(
+        &self,
+        file_name: &str,
+        verbose: bool,
+        copyright: &str,
+    ) -> impl New<Output = ffi::draco::Status> {
+        ffi::draco_extra::gltf_encode_mesh_to_file(&self.0, file_name, verbose, copyright)
+    }
+
+    unsafe fn encode_to_buffer(
+        &self,
+        verbose: bool,
+        copyright: &str,
+        out_buffer: *mut ffi::draco::EncoderBuffer,
+    ) -> impl New<Output = ffi::draco::Status> {
+        ffi::draco_extra::gltf_encode_mesh_to_buffer(&self.0, verbose, copyright, out_buffer)
+    }
+}
+
+impl GltfGeometry for Mesh {}
+
+impl sealed::Sealed for Scene {
+    fn encode_to_file(
+        &self,
+        file_name: &str,
+        verbose: bool,
+        copyright: &str,
+    ) -> impl New<Output = ffi::draco::Status> {
+        ffi::draco_extra::gltf_encode_scene_to_file(&self.0, file_name, verbose, copyright)
+    }
+
+    unsafe fn encode_to_buffer(
+        &self,
+        verbose: bool,
+        copyright: &str,
+        out_buffer: *mut ffi::draco::EncoderBuffer,
+    ) -> impl New<Output = ffi::draco::Status> {
+        ffi::draco_extra::gltf_encode_scene_to_buffer(&self.0, verbose, copyright, out_buffer)
+    }
+}
+
+impl GltfGeometry for Scene {}
+
+/// Writes meshes and scenes as glTF 2.0. Meshes with compression enabled
+/// (see [`Mesh::set_compression_options`]) are stored with the
+/// `KHR_draco_mesh_compression` extension.
+///
+/// ```ignore
+/// let mut mesh = Mesh::read_gltf("input.gltf")?;
+/// mesh.set_compression_options(Some(&DracoCompressionOptions::default()))?;
+/// GltfEncoder::new().encode_to_file(&mesh, "output.glb")?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GltfEncoder {
+    output_type: GltfOutputType,
+    copyright: String,
+}
+
+impl GltfEncoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_output_type(mut self, output_type: GltfOutputType) -> Self {
+        self.output_type = output_type;
+        self
+    }
+
+    /// Sets the copyright notice stored in the asset information.
+    pub fn set_copyright(mut self, copyright: &str) -> Self {
+        self.copyright = copyright.to_owned();
+        self
+    }
+
+    /// Writes the geometry to a file: GLB if the extension of `path` is
+    /// `.glb`, glTF otherwise. For glTF, the binary data and textures are
+    /// written next to the file.
+    pub fn encode_to_file(
+        &self,
+        geometry: &impl GltfGeometry,
+        path: impl AsRef<Path>,
+    ) -> DracoResult<()> {
+        let path = path_to_str(path.as_ref(), Operation::WriteFile)?;
+        let status = geometry
+            .encode_to_file(
+                path,
+                self.output_type == GltfOutputType::Verbose,
+                &self.copyright,
+            )
+            .within_box();
+        DracoError::check(&status, Operation::WriteFile)
+    }
+
+    /// Encodes the geometry as GLB, with all resources embedded.
+    pub fn encode_to_vec(&self, geometry: &impl GltfGeometry) -> DracoResult<Vec<u8>> {
+        let mut buffer = EncoderBuffer::new();
+        let status = unsafe {
+            geometry
+                .encode_to_buffer(
+                    self.output_type == GltfOutputType::Verbose,
+                    &self.copyright,
+                    buffer.as_mut_ptr(),
+                )
+                .within_box()
+        };
+        DracoError::check(&status, Operation::Encode)?;
+        Ok(buffer.as_slice().to_vec())
+    }
+}
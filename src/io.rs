@@ -0,0 +1,267 @@
+use crate::prelude::*;
+use autocxx::prelude::*;
+use std::{
+    borrow::Cow,
+    ffi::{c_char, c_void},
+    io::Write,
+    panic::AssertUnwindSafe,
+    path::Path,
+    sync::{Arc, Once, PoisonError, RwLock},
+};
+
+/// Options used when reading a mesh from a file, see
+/// [`Mesh::read_from_file_with_options`](crate::mesh::Mesh::read_from_file_with_options).
+///
+/// Both options only affect Wavefront OBJ files.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct ReadOptions {
+    /// Reads material and object names into the mesh metadata.
+    pub use_metadata: bool,
+    /// Keeps the original polygons in an attribute when faces with more than
+    /// three vertices are triangulated.
+    pub preserve_polygons: bool,
+}
+
+impl ReadOptions {
+    pub(crate) fn to_draco(self) -> UniquePtr<ffi::draco::Options> {
+        let mut options = ffi::draco::Options::new().within_unique_ptr();
+        options.pin_mut().SetBool("use_metadata", self.use_metadata);
+        options
+            .pin_mut()
+            .SetBool("preserve_polygons", self.preserve_polygons);
+        options
+    }
+}
+
+/// File formats geometry can be read from and written to, see
+/// [`Mesh::from_bytes`](crate::mesh::Mesh::from_bytes) and
+/// [`Mesh::write_to_file`](crate::mesh::Mesh::write_to_file).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// Stanford PLY, binary
+    Ply = 0,
+    /// Wavefront OBJ
+    Obj = 1,
+    /// Binary STL, meshes only
+    Stl = 2,
+    /// Draco compressed data, using the default encoder settings
+    Drc = 3,
+}
+
+// Writes `data` to a file, replacing it if it exists. The file is opened by
+// draco, so that the registered file systems are used like for every other
+// file draco writes.
+pub(crate) fn write_file(path: &Path, data: &[u8]) -> DracoResult<()> {
+    let file_name = path_to_str(path, Operation::WriteFile)?;
+    let written =
+        unsafe { ffi::draco_extra::write_buffer_to_file(file_name, data.as_ptr(), data.len()) };
+    if !written {
+        return Err(DracoError::Io {
+            operation: Operation::WriteFile,
+            message: format!("Unable to write {}", path.display()),
+        });
+    }
+    Ok(())
+}
+
+// Turns the `mtllib` statements of OBJ data into comments: when decoding
+// from memory, draco would still open the referenced material libraries
+// relative to the working directory, which must not happen for untrusted
+// input. Data in other formats is passed through unchanged.
+//
+// Lines are split and indentation is skipped exactly like draco's parser
+// does: lines end at '\r' or '\n', and whitespace is the C `isspace` set.
+pub(crate) fn sanitize_input(data: &[u8], format: Format) -> Cow<'_, [u8]> {
+    if format != Format::Obj {
+        return Cow::Borrowed(data);
+    }
+    let is_space = |c: &u8| matches!(c, b' ' | b'\t' | b'\n' | b'\x0B' | b'\x0C' | b'\r');
+    let mut data = data.to_vec();
+    for line in data.split_mut(|&c| c == b'\r' || c == b'\n') {
+        let indent = line.iter().take_while(|c| is_space(c)).count();
+        if line[indent..].starts_with(b"mtllib") {
+            line[indent] = b'#';
+        }
+    }
+    Cow::Owned(data)
+}
+
+// draco takes file names as `std::string`, which cannot hold every path the
+// OS accepts; such paths are rejected rather than mangled.
+pub(crate) fn path_to_str(path: &Path, operation: Operation) -> DracoResult<&str> {
+    path.to_str().ok_or_else(|| DracoError::InvalidParameter {
+        operation,
+        message: format!("Path is not valid UTF-8: {}", path.display()),
+    })
+}
+
+/// A source of files for draco, e.g. an in-memory map, an archive or an
+/// asset cache.
+///
+/// Once registered with [`register_file_system`], file systems serve the
+/// file accesses made by draco that the real disk cannot serve, including
+/// the material libraries and textures referenced by OBJ files.
+///
+/// The disk always takes precedence: draco registers its own disk access
+/// when the program starts, and offers no way to consult anything before
+/// it. A path that exists on disk is read from the disk, and a path whose
+/// directory exists on disk is written to the disk, without reaching any
+/// file system. Virtual files should therefore use a prefix that does not
+/// exist on disk (e.g. `assets:/`). The remaining accesses are offered to
+/// the file systems in registration order.
+///
+/// With the `transcoder` feature, draco creates the missing directories of
+/// the files it writes, so writes only reach the file systems when the
+/// directory cannot be created.
+pub trait FileSystem: Send + Sync {
+    /// Returns the content of the file at `path`, or `None` to let the next
+    /// file system handle it.
+    fn read(&self, path: &str) -> Option<Vec<u8>>;
+
+    /// Opens the file at `path` for writing, or returns `None` to let the
+    /// next file system handle it. File systems are read-only by default.
+    ///
+    /// draco does not report errors occurring when the file is closed, so
+    /// the returned writer should not defer failures to `flush` or `drop`.
+    fn create(&self, _path: &str) -> Option<Box<dyn std::io::Write + Send>> {
+        None
+    }
+}
+
+impl FileSystem for std::collections::HashMap<String, Vec<u8>> {
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        self.get(path).cloned()
+    }
+}
+
+static FILE_SYSTEMS: RwLock<Vec<Arc<dyn FileSystem>>> = RwLock::new(Vec::new());
+
+/// Makes a file system available to draco for the rest of the program, see
+/// [`FileSystem`] for the order in which files are looked up.
+pub fn register_file_system(file_system: impl FileSystem + 'static) {
+    static REGISTER_WITH_DRACO: Once = Once::new();
+    REGISTER_WITH_DRACO.call_once(|| {
+        assert!(
+            ffi::draco_extra::register_rust_file_system(),
+            "failed to register the file system with draco"
+        );
+    });
+    FILE_SYSTEMS
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(Arc::new(file_system));
+}
+
+// Calls `f` on the registered file systems until it returns a value. A file
+// system that panics is skipped, as the panic cannot unwind through draco.
+// The lock is released before calling into the file systems, so that they
+// can register other file systems or use draco themselves.
+fn find_in_file_systems<T>(f: impl Fn(&dyn FileSystem) -> Option<T>) -> Option<T> {
+    let file_systems = FILE_SYSTEMS
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    file_systems.iter().find_map(|file_system| {
+        std::panic::catch_unwind(AssertUnwindSafe(|| f(file_system.as_ref())))
+            .ok()
+            .flatten()
+    })
+}
+
+unsafe fn file_name<'a>(file_name: *const c_char, file_name_size: usize) -> Option<&'a str> {
+    std::str::from_utf8(std::slice::from_raw_parts(
+        file_name as *const u8,
+        file_name_size,
+    ))
+    .ok()
+}
+
+// The functions below are called by the reader and writer defined in
+// `extra.h`. Handles are boxed Rust values, released by the matching
+// `close` function.
+
+#[no_mangle]
+unsafe extern "C" fn draco_rs_open_reader(
+    file_name_ptr: *const c_char,
+    file_name_size: usize,
+    data: *mut *const u8,
+    size: *mut usize,
+) -> *mut c_void {
+    let Some(path) = file_name(file_name_ptr, file_name_size) else {
+        return std::ptr::null_mut();
+    };
+    match find_in_file_systems(|file_system| file_system.read(path)) {
+        Some(content) => {
+            let content = Box::new(content);
+            *data = content.as_ptr();
+            *size = content.len();
+            Box::into_raw(content) as *mut c_void
+        }
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn draco_rs_close_reader(handle: *mut c_void) {
+    drop(Box::from_raw(handle as *mut Vec<u8>));
+}
+
+type FileWriter = Box<dyn std::io::Write + Send>;
+
+#[no_mangle]
+unsafe extern "C" fn draco_rs_open_writer(
+    file_name_ptr: *const c_char,
+    file_name_size: usize,
+) -> *mut c_void {
+    let Some(path) = file_name(file_name_ptr, file_name_size) else {
+        return std::ptr::null_mut();
+    };
+    match find_in_file_systems(|file_system| file_system.create(path)) {
+        Some(writer) => Box::into_raw(Box::new(writer)) as *mut c_void,
+        None => std::ptr::null_mut(),
+    }
+}
+
+#[no_mangle]
+unsafe extern "C" fn draco_rs_write(handle: *mut c_void, data: *const c_char, size: usize) -> bool {
+    let writer = &mut *(handle as *mut FileWriter);
+    let data = std::slice::from_raw_parts(data as *const u8, size);
+    std::panic::catch_unwind(AssertUnwindSafe(|| writer.write_all(data).is_ok())).unwrap_or(false)
+}
+
+#[no_mangle]
+unsafe extern "C" fn draco_rs_close_writer(handle: *mut c_void) {
+    let mut writer = Box::from_raw(handle as *mut FileWriter);
+    let _ = std::panic::catch_unwind(AssertUnwindSafe(move || {
+        let _ = writer.flush();
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_input_comments_out_material_libraries() {
+        for (input, expected) in [
+            (&b"mtllib a.mtl\nv 0 0 0"[..], &b"#tllib a.mtl\nv 0 0 0"[..]),
+            (b"  \tmtllib a.mtl", b"  \t#tllib a.mtl"),
+            (b"#\rmtllib /etc/x.mtl", b"#\r#tllib /etc/x.mtl"),
+            (b"v 0 0 0\r\nmtllib a.mtl", b"v 0 0 0\r\n#tllib a.mtl"),
+            (b"\x0Bmtllib x", b"\x0B#tllib x"),
+            (b"\x0C mtllib x", b"\x0C #tllib x"),
+            (b"o mtllib", b"o mtllib"),
+        ] {
+            assert_eq!(sanitize_input(input, Format::Obj).as_ref(), expected);
+        }
+    }
+
+    #[test]
+    fn sanitize_input_ignores_other_formats() {
+        let data = b"mtllib a.mtl";
+        assert!(matches!(
+            sanitize_input(data, Format::Ply),
+            Cow::Borrowed(borrowed) if borrowed == data
+        ));
+    }
+}
@@ -3,7 +3,17 @@ mod bindgen;
 mod converter;
 pub mod decode;
 pub mod encode;
+pub mod geometry;
+#[cfg(feature = "transcoder")]
+pub mod gltf;
+pub mod io;
+pub mod mesh;
+pub mod metadata;
 pub mod pointcloud;
+#[cfg(feature = "transcoder")]
+pub mod scene;
+#[cfg(feature = "transcoder")]
+pub mod transcoder;
 mod utils;
 
 pub mod prelude {
@@ -12,9 +22,18 @@ pub mod prelude {
         // pub use crate::bindgen::prelude::ffi::draco_extra;
         // pub use crate::bindgen_extra::prelude::ffi::draco_extra;
     }
-    // pub use crate::attribute::NonOwningPointAttribute;
+    pub use crate::attribute::{
+        AttributeError, DracoComponent, PointAttributeRef, QuantizationParameters, TypedAttrId,
+    };
     pub use crate::converter::StatusOr;
-    pub use crate::decode::{Decoder, DecoderBuffer};
-    pub use crate::encode::{Encoder, EncoderBuffer};
+    pub use crate::decode::{Decoder, DecoderBuffer, EncodedGeometryType};
+    pub use crate::encode::{
+        Encoder, EncoderBuffer, EncodingMethod, ExpertEncoder, MeshEdgebreakerMethod,
+        MeshEncoderMethod, PointCloudEncodingMethod, PredictionSchemeMethod,
+    };
+    pub use crate::geometry::{AsPointCloud, Geometry};
+    pub use crate::metadata::Metadata;
+    #[cfg(feature = "transcoder")]
+    pub use crate::scene::Scene;
     pub use crate::utils::*;
 }
@@ -0,0 +1,436 @@
+use crate::{
+    attribute::{check_builder_attribute, is_named_attribute_type},
+    decode::{Decoder, DecoderBuffer},
+    encode::{Encoder, EncoderBuffer},
+    io::{path_to_str, sanitize_input, write_file, Format, ReadOptions},
+    prelude::*,
+};
+use autocxx::prelude::*;
+use std::path::Path;
+
+impl From<u32> for ffi::draco::FaceIndex {
+    fn from(val: u32) -> Self {
+        ffi::draco::FaceIndex { val }
+    }
+}
+
+impl From<usize> for ffi::draco::FaceIndex {
+    fn from(val: usize) -> Self {
+        ffi::draco::FaceIndex { val: val as u32 }
+    }
+}
+
+impl From<i32> for ffi::draco::FaceIndex {
+    fn from(val: i32) -> Self {
+        ffi::draco::FaceIndex { val: val as u32 }
+    }
+}
+
+pub type Mesh = WrappedDracoObject<ffi::draco::Mesh>;
+
+impl Default for Mesh {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Mesh {
+    pub fn new() -> Self {
+        let mesh = ffi::draco::Mesh::new().within_unique_ptr();
+        Self(mesh)
+    }
+
+    /// Creates a mesh with `num_points` points and faces read from an index
+    /// buffer, three point indices per face (e.g. `u16` or `u32` GPU indices).
+    ///
+    /// Attribute values are then added per point with
+    /// [`AsPointCloud::add_attribute`], so points shared between faces stay
+    /// shared:
+    ///
+    /// ```ignore
+    /// let mut mesh = Mesh::from_indices(positions.len() as u32, &indices)?;
+    /// let position = mesh.add_attribute(GeometryAttribute_Type::POSITION, &positions)?;
+    /// let normal = mesh.add_attribute(GeometryAttribute_Type::NORMAL, &normals)?;
+    /// ```
+    pub fn from_indices<I>(num_points: u32, indices: &[I]) -> Result<Self, AttributeError>
+    where
+        I: Copy + Into<u32>,
+    {
+        if indices.len() % 3 != 0 {
+            return Err(AttributeError::InvalidIndexCount(indices.len()));
+        }
+        let indices: Vec<u32> = indices.iter().map(|&i| i.into()).collect();
+        if let Some(&point) = indices.iter().find(|&&i| i >= num_points) {
+            return Err(AttributeError::PointOutOfRange { point, num_points });
+        }
+
+        let mut mesh = Self::new();
+        mesh.as_point_cloud_mut().set_num_points(num_points);
+        unsafe {
+            ffi::draco_extra::mesh_set_faces(
+                mesh.0.pin_mut(),
+                indices.as_ptr(),
+                (indices.len() / 3) as u32,
+            );
+        }
+        Ok(mesh)
+    }
+
+    pub fn num_faces(&self) -> u32 {
+        self.0.num_faces()
+    }
+
+    /// Returns the point indices of the three corners of the given face,
+    /// or `None` if the face index is out of range.
+    pub fn face(&self, face_index: impl Into<ffi::draco::FaceIndex>) -> Option<[u32; 3]> {
+        let face_id = face_index.into().val;
+        if face_id >= self.num_faces() {
+            return None;
+        }
+        Some(std::array::from_fn(|corner| {
+            ffi::draco_extra::mesh_face_point(&self.0, face_id, corner as i32)
+        }))
+    }
+
+    /// Iterates over the point indices of all faces of the mesh
+    pub fn faces(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        (0..self.num_faces()).filter_map(|i| self.face(i))
+    }
+
+    /// Encode the mesh to an encoder buffer
+    pub fn to_buffer(&self, encoder: &mut Encoder) -> DracoResult<EncoderBuffer> {
+        let mut buffer = EncoderBuffer::new();
+
+        let status = unsafe {
+            encoder
+                .0
+                .pin_mut()
+                .EncodeMeshToBuffer(self.0.as_ref().unwrap(), buffer.as_mut_ptr())
+                .within_box()
+        };
+
+        DracoError::check(&status, Operation::Encode)?;
+        Ok(buffer)
+    }
+
+    /// Decode a mesh from a decoder buffer
+    ///
+    /// # Safety
+    ///
+    /// The decoder buffer must contains valid memory
+    pub fn from_buffer(decoder: &mut Decoder, buffer: &mut DecoderBuffer<'_>) -> DracoResult<Self> {
+        let mut status_or = unsafe {
+            decoder
+                .decoder
+                .pin_mut()
+                .DecodeMeshFromBuffer(buffer.as_mut_ptr())
+        };
+        Ok(Self(status_or.pin_mut().into_result(Operation::Decode)?))
+    }
+
+    /// Reads a mesh from a file. The format is chosen from the file extension:
+    /// `.obj`, `.ply` and `.stl` are supported, any other file is decoded as
+    /// draco data.
+    pub fn read_from_file(path: impl AsRef<Path>) -> DracoResult<Self> {
+        Self::read_from_file_with_options(path, ReadOptions::default())
+    }
+
+    /// Reads a mesh from a file, see [`Self::read_from_file`].
+    pub fn read_from_file_with_options(
+        path: impl AsRef<Path>,
+        options: ReadOptions,
+    ) -> DracoResult<Self> {
+        let path = path_to_str(path.as_ref(), Operation::ReadFile)?;
+        let mut status_or = ffi::draco::ReadMeshFromFile2(path, &options.to_draco());
+        Ok(Self(status_or.pin_mut().into_result(Operation::ReadFile)?))
+    }
+
+    /// Decodes a mesh from the content of a file in the given format, without
+    /// accessing the file system.
+    ///
+    /// Material libraries referenced by OBJ data are ignored, so material ids
+    /// follow the order in which materials are first used.
+    pub fn from_bytes(data: &[u8], format: Format) -> DracoResult<Self> {
+        Self::from_bytes_with_options(data, format, ReadOptions::default())
+    }
+
+    /// Decodes a mesh from the content of a file, see [`Self::from_bytes`].
+    pub fn from_bytes_with_options(
+        data: &[u8],
+        format: Format,
+        options: ReadOptions,
+    ) -> DracoResult<Self> {
+        if format == Format::Drc {
+            return Self::from_buffer(&mut Decoder::new(), &mut DecoderBuffer::from_buffer(data));
+        }
+        let data = sanitize_input(data, format);
+        let mut buffer = DecoderBuffer::from_buffer(&data);
+        let mut mesh = Self::new();
+        let status = unsafe {
+            ffi::draco_extra::mesh_decode_from_format(
+                buffer.as_mut_ptr(),
+                format as i32,
+                options.use_metadata,
+                options.preserve_polygons,
+                mesh.0.pin_mut(),
+            )
+            .within_box()
+        };
+        DracoError::check(&status, Operation::Decode)?;
+        Ok(mesh)
+    }
+
+    /// Reads a glTF or GLB file, merging all the meshes of its scene into a
+    /// single mesh with the node transforms applied.
+    #[cfg(feature = "transcoder")]
+    pub fn read_gltf(path: impl AsRef<Path>) -> DracoResult<Self> {
+        let path = path_to_str(path.as_ref(), Operation::ReadFile)?;
+        let mut decoder = ffi::draco::GltfDecoder::new().within_unique_ptr();
+        let mut status_or = decoder.pin_mut().DecodeFromFile(path);
+        Ok(Self(status_or.pin_mut().into_result(Operation::ReadFile)?))
+    }
+
+    /// Decodes GLB data from memory, see [`Self::read_gltf`]. Resources
+    /// must be embedded in the binary buffer.
+    #[cfg(feature = "transcoder")]
+    pub fn from_glb_bytes(data: &[u8]) -> DracoResult<Self> {
+        let mut buffer = DecoderBuffer::from_buffer(data);
+        let mut decoder = ffi::draco::GltfDecoder::new().within_unique_ptr();
+        let mut status_or = unsafe { decoder.pin_mut().DecodeFromBuffer(buffer.as_mut_ptr()) };
+        Ok(Self(status_or.pin_mut().into_result(Operation::Decode)?))
+    }
+
+    /// Enables compression with the given options when the mesh is written
+    /// to glTF by a [`GltfEncoder`](crate::gltf::GltfEncoder), or disables it
+    /// with `None`.
+    #[cfg(feature = "transcoder")]
+    pub fn set_compression_options(
+        &mut self,
+        options: Option<&crate::gltf::DracoCompressionOptions>,
+    ) -> DracoResult<()> {
+        match options {
+            Some(options) => {
+                let options = options.to_draco()?;
+                let mut pc = self.as_point_cloud_mut();
+                pc.as_mut().SetCompressionOptions(&options);
+                pc.SetCompressionEnabled(true);
+            }
+            None => self.as_point_cloud_mut().SetCompressionEnabled(false),
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "transcoder")]
+    pub fn is_compression_enabled(&self) -> bool {
+        self.as_point_cloud().IsCompressionEnabled()
+    }
+
+    /// Writes the mesh in the given format and returns the file content.
+    pub fn write_to_vec(&self, format: Format) -> DracoResult<Vec<u8>> {
+        let buffer = match format {
+            Format::Drc => self.to_buffer(&mut Encoder::default())?,
+            _ => {
+                let mut buffer = EncoderBuffer::new();
+                let status = unsafe {
+                    ffi::draco_extra::mesh_encode_to_format(
+                        &self.0,
+                        format as i32,
+                        buffer.as_mut_ptr(),
+                    )
+                    .within_box()
+                };
+                DracoError::check(&status, Operation::Encode)?;
+                buffer
+            }
+        };
+        Ok(buffer.as_slice().to_vec())
+    }
+
+    /// Writes the mesh to a file in the given format, regardless of the
+    /// extension of `path`.
+    pub fn write_to_file(&self, path: impl AsRef<Path>, format: Format) -> DracoResult<()> {
+        write_file(path.as_ref(), &self.write_to_vec(format)?)
+    }
+}
+
+impl AsPointCloud for Mesh {
+    fn as_point_cloud(&self) -> &ffi::draco::PointCloud {
+        ffi::draco_extra::mesh_as_point_cloud(&self.0)
+    }
+
+    fn as_point_cloud_mut(&mut self) -> std::pin::Pin<&mut ffi::draco::PointCloud> {
+        ffi::draco_extra::mesh_as_point_cloud_mut(self.0.pin_mut())
+    }
+}
+
+/// Builds a mesh face by face, where every face has its own three corners
+/// (a "triangle soup"). Points shared between faces are merged when the mesh
+/// is built.
+pub struct MeshBuilder {
+    builder: UniquePtr<ffi::draco::TriangleSoupMeshBuilder>,
+    num_faces: u32,
+    // data type and number of components of every attribute, indexed by attribute id
+    attributes: Vec<(ffi::draco::DataType, usize)>,
+    // names attached to the attributes when the mesh is built
+    names: Vec<(AttrId, String)>,
+}
+
+impl MeshBuilder {
+    /// The largest number of faces a builder accepts. draco stores the number
+    /// of corners, three per face, in an `i32`.
+    pub const MAX_NUM_FACES: u32 = i32::MAX as u32 / 3;
+
+    /// Creates a builder for a mesh with the given number of faces, see
+    /// [`Self::start`].
+    pub fn new(num_faces: u32) -> Result<Self, AttributeError> {
+        let mut builder = Self {
+            builder: ffi::draco::TriangleSoupMeshBuilder::new().within_unique_ptr(),
+            num_faces: 0,
+            attributes: Vec::new(),
+            names: Vec::new(),
+        };
+        builder.start(num_faces)?;
+        Ok(builder)
+    }
+
+    /// Starts building a new mesh with the given number of faces, discarding
+    /// any previously added attribute. Counts above [`Self::MAX_NUM_FACES`]
+    /// are rejected.
+    pub fn start(&mut self, num_faces: u32) -> Result<(), AttributeError> {
+        if num_faces > Self::MAX_NUM_FACES {
+            return Err(AttributeError::TooManyFaces(num_faces));
+        }
+        self.builder.pin_mut().Start((num_faces as i32).into());
+        self.num_faces = num_faces;
+        self.attributes.clear();
+        self.names.clear();
+        Ok(())
+    }
+
+    pub fn num_faces(&self) -> u32 {
+        self.num_faces
+    }
+
+    /// Adds an attribute with `N` components of type `T`.
+    ///
+    /// Fails with [`AttributeError::NotAdded`] if `attribute_type` is not a
+    /// real attribute type (`INVALID` or `NAMED_ATTRIBUTES_COUNT`).
+    pub fn add_attribute<T: DracoComponent, const N: usize>(
+        &mut self,
+        attribute_type: ffi::draco::GeometryAttribute_Type,
+    ) -> Result<TypedAttrId<T, N>, AttributeError> {
+        // draco records the type of the attribute even when it fails to add
+        // it, which would shift the ids of the following attributes
+        if !is_named_attribute_type(attribute_type) {
+            return Err(AttributeError::NotAdded(attribute_type));
+        }
+        let attr_id: AttrId = self
+            .builder
+            .pin_mut()
+            .AddAttribute(
+                attribute_type,
+                TypedAttrId::<T, N>::NUM_COMPONENTS,
+                T::DATA_TYPE,
+            )
+            .into();
+        if attr_id.0 < 0 {
+            return Err(AttributeError::NotAdded(attribute_type));
+        }
+        self.attributes.push((T::DATA_TYPE, N));
+        Ok(TypedAttrId::new(attr_id))
+    }
+
+    /// Adds an attribute like [`Self::add_attribute`], and stores `name` in
+    /// its metadata so that it can be found again with
+    /// [`AsPointCloud::attribute_by_name`] after decoding.
+    pub fn add_named_attribute<T: DracoComponent, const N: usize>(
+        &mut self,
+        attribute_type: ffi::draco::GeometryAttribute_Type,
+        name: &str,
+    ) -> Result<TypedAttrId<T, N>, AttributeError> {
+        let attr_id = self.add_attribute(attribute_type)?;
+        self.names.push((attr_id.id(), name.to_owned()));
+        Ok(attr_id)
+    }
+
+    fn check_face(&self, face_id: u32) -> Result<(), AttributeError> {
+        if face_id >= self.num_faces {
+            return Err(AttributeError::FaceOutOfRange {
+                face: face_id,
+                num_faces: self.num_faces,
+            });
+        }
+        Ok(())
+    }
+
+    /// Sets the values of the attribute `attr_id` for the three corners of a face.
+    pub fn set_attribute_values_for_face<T: DracoComponent, const N: usize>(
+        &mut self,
+        attr_id: TypedAttrId<T, N>,
+        face_index: impl Into<ffi::draco::FaceIndex>,
+        corner_values: &[[T; N]; 3],
+    ) -> Result<(), AttributeError> {
+        let face_id = face_index.into().val;
+        check_builder_attribute(&self.attributes, attr_id)?;
+        self.check_face(face_id)?;
+        unsafe {
+            self.builder.pin_mut().SetAttributeValuesForFace(
+                attr_id.id().into(),
+                face_id.into(),
+                corner_values[0].as_ptr() as *const c_void,
+                corner_values[1].as_ptr() as *const c_void,
+                corner_values[2].as_ptr() as *const c_void,
+            );
+        }
+        Ok(())
+    }
+
+    /// Sets the value of the attribute `attr_id` for a whole face, i.e. the
+    /// same value is used by all three corners.
+    pub fn set_per_face_attribute_value_for_face<T: DracoComponent, const N: usize>(
+        &mut self,
+        attr_id: TypedAttrId<T, N>,
+        face_index: impl Into<ffi::draco::FaceIndex>,
+        value: &[T; N],
+    ) -> Result<(), AttributeError> {
+        let face_id = face_index.into().val;
+        check_builder_attribute(&self.attributes, attr_id)?;
+        self.check_face(face_id)?;
+        unsafe {
+            self.builder.pin_mut().SetPerFaceAttributeValueForFace(
+                attr_id.id().into(),
+                face_id.into(),
+                value.as_ptr() as *const c_void,
+            );
+        }
+        Ok(())
+    }
+
+    /// Finalizes the mesh. Returns `None` if draco fails to deduplicate the
+    /// attribute values.
+    pub fn build(mut self) -> Option<Mesh> {
+        let mesh = self.builder.pin_mut().Finalize();
+        if mesh.is_null() {
+            return None;
+        }
+        let mut mesh = Mesh(mesh);
+        // names are only recorded for attributes draco has added, and
+        // finalizing keeps every attribute, so naming them cannot fail
+        for (attr_id, name) in &self.names {
+            let _ = mesh.set_attribute_name(*attr_id, name);
+        }
+        Some(mesh)
+    }
+}
+
+impl GetDracoInner for MeshBuilder {
+    type Inner = ffi::draco::TriangleSoupMeshBuilder;
+
+    fn get_inner(&self) -> &UniquePtr<Self::Inner> {
+        &self.builder
+    }
+
+    fn get_inner_mut(&mut self) -> &mut UniquePtr<Self::Inner> {
+        &mut self.builder
+    }
+}
@@ -0,0 +1,210 @@
+use crate::prelude::ffi;
+use autocxx::prelude::*;
+use std::collections::{btree_map::Entry, BTreeMap};
+
+// Attribute metadata entry holding the name of the attribute, see
+// `AsPointCloud::attribute_by_name`.
+pub(crate) const ATTRIBUTE_NAME_ENTRY: &str = "name";
+
+/// Named entries attached to a geometry or to one of its attributes, see
+/// [`AsPointCloud::metadata`](crate::geometry::AsPointCloud::metadata).
+///
+/// Like in draco, entries are stored as raw bytes and carry no type
+/// information: a value has to be read back with the getter matching the
+/// setter it was written with. Entries can be grouped into named
+/// sub-metadata, which can be nested.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Metadata {
+    entries: BTreeMap<String, Vec<u8>>,
+    sub_metadatas: BTreeMap<String, Metadata>,
+}
+
+impl Metadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of entries, not counting the sub-metadata.
+    pub fn num_entries(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.sub_metadatas.is_empty()
+    }
+
+    /// Iterates over the entries and their raw bytes, sorted by name.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.entries
+            .iter()
+            .map(|(name, data)| (name.as_str(), data.as_slice()))
+    }
+
+    /// Iterates over the sub-metadata, sorted by name.
+    pub fn sub_metadatas(&self) -> impl Iterator<Item = (&str, &Metadata)> {
+        self.sub_metadatas
+            .iter()
+            .map(|(name, metadata)| (name.as_str(), metadata))
+    }
+
+    pub fn contains_entry(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+
+    /// Removes an entry, returning its raw bytes if it existed.
+    pub fn remove_entry(&mut self, name: &str) -> Option<Vec<u8>> {
+        self.entries.remove(name)
+    }
+
+    // Every setter replaces the existing entry with the same name, if any.
+
+    pub fn add_int(&mut self, name: impl Into<String>, value: i32) {
+        self.entries
+            .insert(name.into(), value.to_ne_bytes().to_vec());
+    }
+
+    pub fn add_int_array(&mut self, name: impl Into<String>, values: &[i32]) {
+        let data = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        self.entries.insert(name.into(), data);
+    }
+
+    pub fn add_double(&mut self, name: impl Into<String>, value: f64) {
+        self.entries
+            .insert(name.into(), value.to_ne_bytes().to_vec());
+    }
+
+    pub fn add_double_array(&mut self, name: impl Into<String>, values: &[f64]) {
+        let data = values.iter().flat_map(|v| v.to_ne_bytes()).collect();
+        self.entries.insert(name.into(), data);
+    }
+
+    pub fn add_string(&mut self, name: impl Into<String>, value: &str) {
+        self.entries.insert(name.into(), value.as_bytes().to_vec());
+    }
+
+    pub fn add_binary(&mut self, name: impl Into<String>, value: &[u8]) {
+        self.entries.insert(name.into(), value.to_vec());
+    }
+
+    /// Adds a named sub-metadata. Returns `false` (and leaves the metadata
+    /// unchanged) if a sub-metadata with the same name already exists.
+    pub fn add_sub_metadata(&mut self, name: impl Into<String>, sub_metadata: Metadata) -> bool {
+        match self.sub_metadatas.entry(name.into()) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(sub_metadata);
+                true
+            }
+        }
+    }
+
+    pub fn sub_metadata(&self, name: &str) -> Option<&Metadata> {
+        self.sub_metadatas.get(name)
+    }
+
+    pub fn sub_metadata_mut(&mut self, name: &str) -> Option<&mut Metadata> {
+        self.sub_metadatas.get_mut(name)
+    }
+
+    // The getters return `None` if the entry does not exist or if its size
+    // does not match the requested type.
+
+    pub fn get_int(&self, name: &str) -> Option<i32> {
+        let data = self.entries.get(name)?;
+        Some(i32::from_ne_bytes(data.as_slice().try_into().ok()?))
+    }
+
+    pub fn get_int_array(&self, name: &str) -> Option<Vec<i32>> {
+        let data = self.entries.get(name)?;
+        if data.len() % 4 != 0 {
+            return None;
+        }
+        Some(
+            data.chunks_exact(4)
+                .map(|chunk| i32::from_ne_bytes(chunk.try_into().unwrap()))
+                .collect(),
+        )
+    }
+
+    pub fn get_double(&self, name: &str) -> Option<f64> {
+        let data = self.entries.get(name)?;
+        Some(f64::from_ne_bytes(data.as_slice().try_into().ok()?))
+    }
+
+    pub fn get_double_array(&self, name: &str) -> Option<Vec<f64>> {
+        let data = self.entries.get(name)?;
+        if data.len() % 8 != 0 {
+            return None;
+        }
+        Some(
+            data.chunks_exact(8)
+                .map(|chunk| f64::from_ne_bytes(chunk.try_into().unwrap()))
+                .collect(),
+        )
+    }
+
+    /// Returns the entry as a string, or `None` if it is not valid UTF-8.
+    pub fn get_string(&self, name: &str) -> Option<&str> {
+        std::str::from_utf8(self.entries.get(name)?).ok()
+    }
+
+    pub fn get_binary(&self, name: &str) -> Option<&[u8]> {
+        self.entries.get(name).map(Vec::as_slice)
+    }
+
+    // Builds the equivalent draco metadata, including the sub-metadata.
+    pub(crate) fn to_draco(&self) -> UniquePtr<ffi::draco::Metadata> {
+        let mut metadata = ffi::draco::Metadata::new().within_unique_ptr();
+        for (name, data) in &self.entries {
+            unsafe {
+                ffi::draco_extra::metadata_add_entry_bytes(
+                    metadata.pin_mut(),
+                    name.as_str(),
+                    data.as_ptr(),
+                    data.len(),
+                );
+            }
+        }
+        for (name, sub_metadata) in &self.sub_metadatas {
+            ffi::draco_extra::metadata_add_sub_metadata(
+                metadata.pin_mut(),
+                name.as_str(),
+                sub_metadata.to_draco(),
+            );
+        }
+        metadata
+    }
+
+    // Copies a draco metadata, including the sub-metadata.
+    pub(crate) fn from_draco(metadata: &ffi::draco::Metadata) -> Self {
+        let mut result = Self::new();
+        for i in 0..metadata.num_entries().0 {
+            let name = ffi::draco_extra::metadata_entry_name(metadata, i)
+                .to_string_lossy()
+                .into_owned();
+            let mut size = 0;
+            let data = unsafe {
+                let ptr = ffi::draco_extra::metadata_entry_data(metadata, i, &mut size);
+                if size == 0 {
+                    Vec::new()
+                } else {
+                    std::slice::from_raw_parts(ptr, size).to_vec()
+                }
+            };
+            result.entries.insert(name, data);
+        }
+        for i in 0..ffi::draco_extra::metadata_num_sub_metadatas(metadata) {
+            let name = ffi::draco_extra::metadata_sub_metadata_name(metadata, i)
+                .to_string_lossy()
+                .into_owned();
+            let sub_metadata =
+                unsafe { ffi::draco_extra::metadata_sub_metadata(metadata, i).as_ref() };
+            if let Some(sub_metadata) = sub_metadata {
+                result
+                    .sub_metadatas
+                    .insert(name, Self::from_draco(sub_metadata));
+            }
+        }
+        result
+    }
+}
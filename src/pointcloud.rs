@@ -1,44 +1,210 @@
 use crate::{
+    attribute::{check_builder_attribute, is_named_attribute_type},
     decode::{Decoder, DecoderBuffer},
     encode::{Encoder, EncoderBuffer},
+    io::{path_to_str, sanitize_input, write_file, Format},
     prelude::*,
 };
 use autocxx::prelude::*;
+use std::path::Path;
 
-pub type PointCloudBuilder = WrappedDracoObject<ffi::draco::PointCloudBuilder>;
+pub struct PointCloudBuilder {
+    builder: UniquePtr<ffi::draco::PointCloudBuilder>,
+    num_points: u32,
+    // data type and number of components of every attribute, indexed by attribute id
+    attributes: Vec<(ffi::draco::DataType, usize)>,
+    // names attached to the attributes when the point cloud is built
+    names: Vec<(AttrId, String)>,
+}
 
 impl PointCloudBuilder {
     pub fn new(num: u32) -> Self {
         let mut builder = ffi::draco::PointCloudBuilder::new().within_unique_ptr();
         builder.pin_mut().Start(num);
-        Self(builder)
+        Self {
+            builder,
+            num_points: num,
+            attributes: Vec::new(),
+            names: Vec::new(),
+        }
+    }
+
+    pub fn num_points(&self) -> u32 {
+        self.num_points
+    }
+
+    /// Adds an attribute with `N` components of type `T` to every point.
+    ///
+    /// Fails with [`AttributeError::NotAdded`] if `attribute_type` is not a
+    /// real attribute type (`INVALID` or `NAMED_ATTRIBUTES_COUNT`).
+    pub fn add_attribute<T: DracoComponent, const N: usize>(
+        &mut self,
+        attribute_type: ffi::draco::GeometryAttribute_Type,
+    ) -> Result<TypedAttrId<T, N>, AttributeError> {
+        let attr_id = self.add_attribute_raw(
+            attribute_type,
+            TypedAttrId::<T, N>::NUM_COMPONENTS,
+            T::DATA_TYPE,
+        )?;
+        Ok(TypedAttrId::new(attr_id))
+    }
+
+    /// Adds an attribute like [`Self::add_attribute`], and stores `name` in
+    /// its metadata so that it can be found again with
+    /// [`AsPointCloud::attribute_by_name`] after decoding.
+    pub fn add_named_attribute<T: DracoComponent, const N: usize>(
+        &mut self,
+        attribute_type: ffi::draco::GeometryAttribute_Type,
+        name: &str,
+    ) -> Result<TypedAttrId<T, N>, AttributeError> {
+        let attr_id = self.add_attribute(attribute_type)?;
+        self.names.push((attr_id.id(), name.to_owned()));
+        Ok(attr_id)
     }
 
-    pub fn add_attribute(
+    /// Adds an attribute with a layout only known at runtime. Values for it
+    /// can only be set through [`Self::add_point_with_ptr`].
+    pub fn add_attribute_raw(
         &mut self,
         attribute_type: ffi::draco::GeometryAttribute_Type,
         num_components: i8,
         data_type: ffi::draco::DataType,
-    ) -> AttrId {
-        self.0
+    ) -> Result<AttrId, AttributeError> {
+        // draco would index its per-type tables with the sentinel types
+        if !is_named_attribute_type(attribute_type) {
+            return Err(AttributeError::NotAdded(attribute_type));
+        }
+        let attr_id: AttrId = self
+            .builder
             .pin_mut()
             .AddAttribute(attribute_type, num_components, data_type)
-            .into()
+            .into();
+        if attr_id.0 < 0 {
+            return Err(AttributeError::NotAdded(attribute_type));
+        }
+        // only attributes that exist in draco get a layout, so that the
+        // layouts stay indexed by attribute id
+        self.attributes.push((data_type, num_components as usize));
+        Ok(attr_id)
+    }
+
+    /// Sets the value of the attribute `attr_id` for a specific point.
+    ///
+    /// Fails if `point` does not have exactly `N` components, `attr_id` does
+    /// not belong to this builder, or the point index is out of range.
+    pub fn add_point<T: DracoComponent, const N: usize>(
+        &mut self,
+        attr_id: TypedAttrId<T, N>,
+        point_index: impl Into<ffi::draco::PointIndex>,
+        point: &[T],
+    ) -> Result<(), AttributeError> {
+        let point_index = point_index.into().val;
+        check_builder_attribute(&self.attributes, attr_id)?;
+        if point.len() != N {
+            return Err(AttributeError::ComponentCountMismatch {
+                expected: N,
+                actual: point.len(),
+            });
+        }
+        if point_index >= self.num_points {
+            return Err(AttributeError::PointOutOfRange {
+                point: point_index,
+                num_points: self.num_points,
+            });
+        }
+        unsafe {
+            self.add_point_with_ptr(attr_id.id(), point_index, point.as_ptr() as *const c_void)
+        };
+        Ok(())
+    }
+
+    /// Sets the values of the attribute `attr_id` for all points at once.
+    ///
+    /// `values` must contain exactly one entry per point.
+    pub fn set_attribute_values<T: DracoComponent, const N: usize>(
+        &mut self,
+        attr_id: TypedAttrId<T, N>,
+        values: &[[T; N]],
+    ) -> Result<(), AttributeError> {
+        check_builder_attribute(&self.attributes, attr_id)?;
+        self.check_num_values(values.len())?;
+        unsafe {
+            self.set_attribute_values_with_ptr(attr_id.id(), values.as_ptr() as *const c_void, 0)
+        };
+        Ok(())
+    }
+
+    /// Sets the values of the attribute `attr_id` for all points from a slice
+    /// of interleaved vertex structs.
+    ///
+    /// `field` selects the attribute value within each vertex, e.g.
+    /// `|v: &Vertex| &v.position`. It must return a reference into the vertex
+    /// it was given, at the same offset for every vertex.
+    pub fn set_attribute_values_strided<T: DracoComponent, const N: usize, V>(
+        &mut self,
+        attr_id: TypedAttrId<T, N>,
+        vertices: &[V],
+        field: impl Fn(&V) -> &[T; N],
+    ) -> Result<(), AttributeError> {
+        check_builder_attribute(&self.attributes, attr_id)?;
+        self.check_num_values(vertices.len())?;
+        let Some(first) = vertices.first() else {
+            return Ok(());
+        };
+        let vertex_ptr = first as *const V as usize;
+        let field_ptr = field(first) as *const [T; N] as usize;
+        // only accept fields that lie entirely within the vertex struct
+        let offset = field_ptr.wrapping_sub(vertex_ptr);
+        if field_ptr < vertex_ptr
+            || offset + std::mem::size_of::<[T; N]>() > std::mem::size_of::<V>()
+        {
+            return Err(AttributeError::NotContiguous);
+        }
+        unsafe {
+            self.set_attribute_values_with_ptr(
+                attr_id.id(),
+                field_ptr as *const c_void,
+                std::mem::size_of::<V>() as i32,
+            )
+        };
+        Ok(())
+    }
+
+    // Checks that one value is given for every point of the builder.
+    fn check_num_values(&self, num_values: usize) -> Result<(), AttributeError> {
+        if num_values != self.num_points as usize {
+            return Err(AttributeError::ValueCountMismatch {
+                expected: self.num_points as usize,
+                actual: num_values,
+            });
+        }
+        Ok(())
     }
 
-    /// Adds the data from the provided slice as the attribute value for a specific point.
+    /// Sets the values of the attribute `attr_id` for all points from the data
+    /// pointed to by the raw pointer.
+    ///
+    /// `stride` is the distance in bytes between the values of two consecutive
+    /// points, or 0 if the values are tightly packed.
     ///
     /// # Safety
     ///
-    /// The `point` slice must point to valid memory with a lifetime at least as long as the `PointCloudBuilder` instance.
-    /// The size and type of the data in the slice must be compatible with the attribute `attr_id`.
-    pub fn add_point<T>(
+    /// - `attr_id` must be an attribute of this builder.
+    /// - `ptr` must point to `num_points` values of the attribute type, each
+    ///   `stride` bytes apart.
+    pub unsafe fn set_attribute_values_with_ptr(
         &mut self,
         attr_id: AttrId,
-        point_index: impl Into<ffi::draco::PointIndex>,
-        point: &[T],
+        ptr: *const c_void,
+        stride: i32,
     ) {
-        unsafe { self.add_point_with_ptr(attr_id, point_index, point.as_ptr() as *const c_void) }
+        // without points, draco allocates no attribute buffer to write to
+        if self.num_points == 0 {
+            return;
+        }
+        self.builder
+            .pin_mut()
+            .SetAttributeValuesForAllPoints(attr_id.into(), ptr, stride.into());
     }
 
     /// Adds the data pointed to by the raw pointer as the attribute value for a specific point.
@@ -47,8 +213,8 @@ impl PointCloudBuilder {
     ///
     /// This function is unsafe because it takes a raw pointer `ptr` to the point data.
     /// It is the caller's responsibility to ensure that:
+    /// - `attr_id` is an attribute of this builder and `point_index` is smaller than the number of points.
     /// - `ptr` is valid and points to memory that is properly aligned for the attribute type.
-    /// - The memory pointed to by `ptr` has a lifetime at least as long as the `PointCloudBuilder` instance.
     /// - The size of the data pointed to by `ptr` is sufficient for the attribute type associated with `attr_id`.
     pub unsafe fn add_point_with_ptr(
         &mut self,
@@ -56,114 +222,243 @@ impl PointCloudBuilder {
         point_index: impl Into<ffi::draco::PointIndex>,
         ptr: *const c_void,
     ) {
-        self.0
+        self.builder
             .pin_mut()
             .SetAttributeValueForPoint(attr_id.into(), point_index.into(), ptr);
     }
 
     pub fn build(mut self, deduplicate_points: bool) -> PointCloud {
-        PointCloud {
-            0: self.0.pin_mut().Finalize(deduplicate_points),
+        let mut pc = PointCloud {
+            0: self.builder.pin_mut().Finalize(deduplicate_points),
+        };
+        // names are only recorded for attributes draco has added, and
+        // finalizing keeps every attribute, so naming them cannot fail
+        for (attr_id, name) in &self.names {
+            let _ = pc.set_attribute_name(*attr_id, name);
         }
+        pc
     }
 }
 
-pub type PointCloud = WrappedDracoObject<ffi::draco::PointCloud>;
+impl GetDracoInner for PointCloudBuilder {
+    type Inner = ffi::draco::PointCloudBuilder;
 
-impl Default for PointCloud {
-    fn default() -> Self {
-        Self::new()
+    fn get_inner(&self) -> &UniquePtr<Self::Inner> {
+        &self.builder
+    }
+
+    fn get_inner_mut(&mut self) -> &mut UniquePtr<Self::Inner> {
+        &mut self.builder
     }
 }
 
-impl PointCloud {
+// Attribute data accumulated on the Rust side by `GrowablePointCloudBuilder`.
+struct GrowableAttribute {
+    attribute_type: ffi::draco::GeometryAttribute_Type,
+    data_type: ffi::draco::DataType,
+    num_components: usize,
+    // size in bytes of a single value
+    stride: usize,
+    data: Vec<u8>,
+}
+
+/// A point cloud builder that does not need to know the number of points
+/// upfront.
+///
+/// Attribute values are accumulated in Rust-owned buffers and handed over to
+/// draco's `PointCloudBuilder` in one go when [`Self::build`] is called.
+///
+/// ```ignore
+/// let mut builder = GrowablePointCloudBuilder::new();
+/// let position = builder.add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)?;
+/// let intensity = builder.add_attribute::<u16, 1>(GeometryAttribute_Type::GENERIC)?;
+/// builder.extend(records, |point, record| {
+///     point.set(position, record.position)?;
+///     point.set(intensity, [record.intensity])
+/// })?;
+/// let pc = builder.build(false);
+/// ```
+#[derive(Default)]
+pub struct GrowablePointCloudBuilder {
+    attributes: Vec<GrowableAttribute>,
+    num_points: u32,
+}
+
+impl GrowablePointCloudBuilder {
     pub fn new() -> Self {
-        let pc = ffi::draco::PointCloud::new().within_unique_ptr();
-        Self(pc)
+        Self::default()
+    }
+
+    pub fn num_points(&self) -> u32 {
+        self.num_points
     }
 
-    // This function returns the attribute id of the attribute with the given name
-    // It stores the value in-place
-    pub fn get_point<T>(
+    /// Adds an attribute with `N` components of type `T`. The returned id is
+    /// also valid for the point cloud produced by [`Self::build`].
+    ///
+    /// Fails with [`AttributeError::NotAdded`] if `attribute_type` is not a
+    /// real attribute type (`INVALID` or `NAMED_ATTRIBUTES_COUNT`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if points have already been added.
+    pub fn add_attribute<T: DracoComponent, const N: usize>(
         &mut self,
-        attr_id: AttrId,
-        point_index: impl Into<ffi::draco::PointIndex>,
-        point_container: &mut [T],
-    ) where
-        T: Default + Copy,
-    {
-        let pa_ptr = self.0.pin_mut().GetAttributeByUniqueId(attr_id.as_u32());
-        unsafe {
-            (*pa_ptr).GetMappedValue(
-                point_index.into(),
-                point_container.as_mut_ptr() as *mut c_void,
-            );
-        };
+        attribute_type: ffi::draco::GeometryAttribute_Type,
+    ) -> Result<TypedAttrId<T, N>, AttributeError> {
+        assert!(
+            self.num_points == 0,
+            "attributes must be added before any point"
+        );
+        if !is_named_attribute_type(attribute_type) {
+            return Err(AttributeError::NotAdded(attribute_type));
+        }
+        self.attributes.push(GrowableAttribute {
+            attribute_type,
+            data_type: T::DATA_TYPE,
+            num_components: TypedAttrId::<T, N>::NUM_COMPONENTS as usize,
+            stride: std::mem::size_of::<[T; N]>(),
+            data: Vec::new(),
+        });
+        Ok(TypedAttrId::new(AttrId(self.attributes.len() as i32 - 1)))
     }
 
-    // This function allocates a new array of type T and fills it with the point data
-    pub fn get_point_alloc<T, const N: usize>(
+    /// Appends one point per record.
+    ///
+    /// `fill` is called for every record and must set a value for every
+    /// attribute of the builder through the given [`GrowablePoint`]. If it
+    /// fails, or leaves an attribute unset, the offending record is discarded
+    /// and the error is returned; points from earlier records are kept.
+    pub fn extend<R, F>(
         &mut self,
-        attr_id: AttrId,
-        point_index: impl Into<ffi::draco::PointIndex>,
-    ) -> [T; N]
+        records: impl IntoIterator<Item = R>,
+        mut fill: F,
+    ) -> Result<(), AttributeError>
     where
-        T: Default + Copy,
+        F: FnMut(&mut GrowablePoint<'_>, R) -> Result<(), AttributeError>,
     {
-        let mut point = [T::default(); N];
-        self.get_point(attr_id, point_index, &mut point);
-        point
-    }
-
-    // Returns the number of named attributes of a given type.
-    pub fn num_named_attributes(&self, attr_type: ffi::draco::GeometryAttribute_Type) -> i32 {
-        self.0.NumNamedAttributes(attr_type)
-    }
-
-    // Returns the id of the i-th named attribute of a given type.
-    pub fn get_named_attribute_id(
-        &self,
-        attr_type: ffi::draco::GeometryAttribute_Type,
-        i: i32,
-    ) -> Option<AttrId> {
-        let id = self.0.GetNamedAttributeId1(attr_type, i.into());
-        if id < 0 {
-            None
-        } else {
-            Some(AttrId(id))
+        for record in records {
+            for attribute in &mut self.attributes {
+                attribute
+                    .data
+                    .resize(attribute.data.len() + attribute.stride, 0);
+            }
+            let num_attributes = self.attributes.len();
+            let mut point = GrowablePoint {
+                attributes: &mut self.attributes,
+                is_set: vec![false; num_attributes],
+            };
+            let result = fill(&mut point, record).and_then(|()| {
+                match point.is_set.iter().position(|is_set| !is_set) {
+                    Some(i) => Err(AttributeError::MissingValue(AttrId(i as i32))),
+                    None => Ok(()),
+                }
+            });
+            if let Err(err) = result {
+                for attribute in &mut self.attributes {
+                    attribute
+                        .data
+                        .truncate(attribute.data.len() - attribute.stride);
+                }
+                return Err(err);
+            }
+            self.num_points += 1;
+        }
+        Ok(())
+    }
+
+    pub fn build(self, deduplicate_points: bool) -> PointCloud {
+        let mut builder = PointCloudBuilder::new(self.num_points);
+        for attribute in &self.attributes {
+            // cannot fail, as `add_attribute` only accepts valid types
+            let Ok(attr_id) = builder.add_attribute_raw(
+                attribute.attribute_type,
+                attribute.num_components as i8,
+                attribute.data_type,
+            ) else {
+                continue;
+            };
+            // `extend` keeps exactly one value per point in every buffer
+            unsafe {
+                builder.set_attribute_values_with_ptr(
+                    attr_id,
+                    attribute.data.as_ptr() as *const c_void,
+                    0,
+                )
+            };
         }
+        builder.build(deduplicate_points)
     }
+}
 
-    // // Returns the i-th named attribute of a given type.
-    // pub fn get_named_attribute(
-    //     &self,
-    //     attr_type: ffi::draco::GeometryAttribute_Type,
-    //     i: i32,
-    // ) -> Option<NonOwningPointAttribute> {
-    //     let attr = self.0.GetNamedAttribute1(attr_type, i.into());
-    //     if attr.is_null() {
-    //         None
-    //     } else {
-    //         Some(NonOwningPointAttribute { ptr: attr })
-    //     }
-    // }
-
-    //   // Returns the named attribute of a given unique id.
-    //     pub fn get_named_attribute_by_unique_id(
-    //         &self,
-    //         attr_type: ffi::draco::GeometryAttribute_Type,
-    //         id: u32,
-    //     ) -> Option<NonOwningPointAttribute> {
-    //         let attr = self.0.GetNamedAttributeByUniqueId(attr_type, id);
-    //         if attr.is_null() {
-    //             None
-    //         } else {
-    //             Some(NonOwningPointAttribute { ptr: attr })
-    //         }
-    //     }
+/// The point currently being added by [`GrowablePointCloudBuilder::extend`].
+pub struct GrowablePoint<'a> {
+    attributes: &'a mut [GrowableAttribute],
+    is_set: Vec<bool>,
+}
 
-    pub fn num_points(&self) -> u32 {
-        self.0.num_points()
+impl GrowablePoint<'_> {
+    /// Sets the value of the attribute `attr_id` for this point.
+    pub fn set<T: DracoComponent, const N: usize>(
+        &mut self,
+        attr_id: TypedAttrId<T, N>,
+        value: [T; N],
+    ) -> Result<(), AttributeError> {
+        let index = usize::try_from(attr_id.id().0)
+            .ok()
+            .filter(|&i| i < self.attributes.len())
+            .ok_or(AttributeError::NotFound(attr_id.id()))?;
+        let attribute = &mut self.attributes[index];
+        if attribute.data_type != T::DATA_TYPE {
+            return Err(AttributeError::DataTypeMismatch {
+                expected: T::DATA_TYPE,
+                actual: attribute.data_type,
+            });
+        }
+        if attribute.num_components != N {
+            return Err(AttributeError::ComponentCountMismatch {
+                expected: attribute.num_components,
+                actual: N,
+            });
+        }
+        // draco components are plain numbers without padding, so their bytes
+        // can be copied as is
+        let bytes =
+            unsafe { std::slice::from_raw_parts(value.as_ptr() as *const u8, attribute.stride) };
+        let start = attribute.data.len() - attribute.stride;
+        attribute.data[start..].copy_from_slice(bytes);
+        self.is_set[index] = true;
+        Ok(())
+    }
+}
+
+/// Collects positions into a point cloud with a single `POSITION` attribute,
+/// whose id is `AttrId(0)`.
+impl<T: DracoComponent, const N: usize> FromIterator<[T; N]> for PointCloud {
+    fn from_iter<I: IntoIterator<Item = [T; N]>>(iter: I) -> Self {
+        let mut builder = GrowablePointCloudBuilder::new();
+        let position = builder
+            .add_attribute::<T, N>(ffi::draco::GeometryAttribute_Type::POSITION)
+            .expect("POSITION is a valid attribute type");
+        builder
+            .extend(iter, |point, value| point.set(position, value))
+            .expect("every point sets its only attribute");
+        builder.build(false)
+    }
+}
+
+pub type PointCloud = WrappedDracoObject<ffi::draco::PointCloud>;
+
+impl Default for PointCloud {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PointCloud {
+    pub fn new() -> Self {
+        let pc = ffi::draco::PointCloud::new().within_unique_ptr();
+        Self(pc)
     }
 
     pub fn len(&self) -> u32 {
@@ -175,7 +470,7 @@ impl PointCloud {
     }
 
     /// Encode the point cloud to an encoder buffer
-    pub fn to_buffer(&self, encoder: &mut Encoder) -> DracoStatusType<EncoderBuffer> {
+    pub fn to_buffer(&self, encoder: &mut Encoder) -> DracoResult<EncoderBuffer> {
         let mut buffer = EncoderBuffer::new();
 
         let status = unsafe {
@@ -183,14 +478,11 @@ impl PointCloud {
                 .0
                 .pin_mut()
                 .EncodePointCloudToBuffer(self.0.as_ref().unwrap(), buffer.as_mut_ptr())
-                .within_unique_ptr()
+                .within_box()
         };
 
-        if status.ok() {
-            Ok(buffer)
-        } else {
-            Err(status.into())
-        }
+        DracoError::check(&status, Operation::Encode)?;
+        Ok(buffer)
     }
 
     /// Decode a point cloud from a decoder buffer
@@ -198,17 +490,82 @@ impl PointCloud {
     /// # Safety
     ///
     /// The decoder buffer must contains valid memory
-    pub fn from_buffer(decoder: &mut Decoder, buffer: &mut DecoderBuffer) -> DracoStatusType<Self> {
+    pub fn from_buffer(decoder: &mut Decoder, buffer: &mut DecoderBuffer<'_>) -> DracoResult<Self> {
         let mut status_or = unsafe {
             decoder
                 .decoder
                 .pin_mut()
-                .DecodePointCloudFromBuffer(buffer.0.as_mut_ptr())
+                .DecodePointCloudFromBuffer(buffer.as_mut_ptr())
         };
-        if status_or.ok() {
-            Ok(Self(status_or.pin_mut().value()))
-        } else {
-            Err(status_or.status().within_unique_ptr().into())
+        Ok(Self(status_or.pin_mut().into_result(Operation::Decode)?))
+    }
+
+    /// Reads a point cloud from a file. The format is chosen from the file
+    /// extension: `.obj` and `.ply` are supported, any other file is decoded
+    /// as draco data. Faces of meshes are ignored.
+    pub fn read_from_file(path: impl AsRef<Path>) -> DracoResult<Self> {
+        let path = path_to_str(path.as_ref(), Operation::ReadFile)?;
+        let mut status_or = ffi::draco::ReadPointCloudFromFile(path);
+        Ok(Self(status_or.pin_mut().into_result(Operation::ReadFile)?))
+    }
+
+    /// Decodes a point cloud from the content of a file in the given format,
+    /// without accessing the file system. [`Format::Stl`] is not supported
+    /// for point clouds.
+    pub fn from_bytes(data: &[u8], format: Format) -> DracoResult<Self> {
+        if format == Format::Drc {
+            return Self::from_buffer(&mut Decoder::new(), &mut DecoderBuffer::from_buffer(data));
         }
+        let data = sanitize_input(data, format);
+        let mut buffer = DecoderBuffer::from_buffer(&data);
+        let mut pc = Self::new();
+        let status = unsafe {
+            ffi::draco_extra::point_cloud_decode_from_format(
+                buffer.as_mut_ptr(),
+                format as i32,
+                pc.0.pin_mut(),
+            )
+            .within_box()
+        };
+        DracoError::check(&status, Operation::Decode)?;
+        Ok(pc)
+    }
+
+    /// Writes the point cloud in the given format and returns the file
+    /// content. [`Format::Stl`] is not supported for point clouds.
+    pub fn write_to_vec(&self, format: Format) -> DracoResult<Vec<u8>> {
+        let buffer = match format {
+            Format::Drc => self.to_buffer(&mut Encoder::default())?,
+            _ => {
+                let mut buffer = EncoderBuffer::new();
+                let status = unsafe {
+                    ffi::draco_extra::point_cloud_encode_to_format(
+                        &self.0,
+                        format as i32,
+                        buffer.as_mut_ptr(),
+                    )
+                    .within_box()
+                };
+                DracoError::check(&status, Operation::Encode)?;
+                buffer
+            }
+        };
+        Ok(buffer.as_slice().to_vec())
+    }
+
+    /// Writes the point cloud to a file in the given format, regardless of
+    /// the extension of `path`.
+    pub fn write_to_file(&self, path: impl AsRef<Path>, format: Format) -> DracoResult<()> {
+        write_file(path.as_ref(), &self.write_to_vec(format)?)
+    }
+}
+
+impl AsPointCloud for PointCloud {
+    fn as_point_cloud(&self) -> &ffi::draco::PointCloud {
+        &self.0
+    }
+
+    fn as_point_cloud_mut(&mut self) -> std::pin::Pin<&mut ffi::draco::PointCloud> {
+        self.0.pin_mut()
     }
 }
@@ -0,0 +1,518 @@
+use crate::{
+    decode::DecoderBuffer, gltf::DracoCompressionOptions, io::path_to_str, mesh::Mesh, prelude::*,
+};
+use autocxx::prelude::*;
+use std::path::Path;
+
+/// A glTF scene: meshes, the node hierarchy referencing them and their
+/// materials. Only available with the `transcoder` feature.
+///
+/// Meshes, mesh groups, nodes and materials are stored in lists and
+/// referenced by their index in these lists. A node places a mesh group in
+/// the scene; the group lists the meshes drawn, each with its material.
+///
+/// ```ignore
+/// let mut scene = Scene::new();
+/// let material = scene.add_material("steel");
+/// let mesh = scene.add_mesh(Mesh::read_from_file("bolt.obj")?);
+/// let group = scene.add_mesh_group("bolt");
+/// scene.add_mesh_instance(group, MeshInstance { mesh, material: Some(material) })?;
+///
+/// let assembly = scene.add_node("assembly");
+/// let bolt = scene.add_node("bolt");
+/// scene.set_node_mesh_group(bolt, Some(group))?;
+/// scene.set_node_transform(bolt, &Transform {
+///     translation: Some([0.0, 0.0, 10.0]),
+///     ..Default::default()
+/// })?;
+/// scene.add_child(assembly, bolt)?;
+/// scene.add_root_node(assembly)?;
+/// ```
+pub type Scene = WrappedDracoObject<ffi::draco::Scene>;
+
+/// An error raised when editing a [`Scene`] with an index that does not
+/// refer to an existing element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SceneError {
+    NodeOutOfRange {
+        node: usize,
+        num_nodes: usize,
+    },
+    MeshOutOfRange {
+        mesh: usize,
+        num_meshes: usize,
+    },
+    MeshGroupOutOfRange {
+        mesh_group: usize,
+        num_mesh_groups: usize,
+    },
+    MaterialOutOfRange {
+        material: usize,
+        num_materials: usize,
+    },
+    /// Linking the nodes would make `child` an ancestor of itself
+    Cycle {
+        parent: usize,
+        child: usize,
+    },
+}
+
+impl std::fmt::Display for SceneError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NodeOutOfRange { node, num_nodes } => write!(
+                f,
+                "node {} is out of range (number of nodes: {})",
+                node, num_nodes
+            ),
+            Self::MeshOutOfRange { mesh, num_meshes } => write!(
+                f,
+                "mesh {} is out of range (number of meshes: {})",
+                mesh, num_meshes
+            ),
+            Self::MeshGroupOutOfRange {
+                mesh_group,
+                num_mesh_groups,
+            } => write!(
+                f,
+                "mesh group {} is out of range (number of mesh groups: {})",
+                mesh_group, num_mesh_groups
+            ),
+            Self::MaterialOutOfRange {
+                material,
+                num_materials,
+            } => write!(
+                f,
+                "material {} is out of range (number of materials: {})",
+                material, num_materials
+            ),
+            Self::Cycle { parent, child } => write!(
+                f,
+                "node {} cannot be a child of node {}, which is itself or one of its descendants",
+                child, parent
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+/// The local transform of a node, relative to its parents.
+///
+/// Like in glTF, a node is transformed either by a matrix or by a
+/// translation, rotation and scale (TRS); parts that are not set are the
+/// identity.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct Transform {
+    /// 4x4 matrix, column-major
+    pub matrix: Option<[f64; 16]>,
+    pub translation: Option<[f64; 3]>,
+    /// Unit quaternion, as (x, y, z, w)
+    pub rotation: Option<[f64; 4]>,
+    pub scale: Option<[f64; 3]>,
+}
+
+/// A mesh drawn by a mesh group, with the index of its material.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MeshInstance {
+    pub mesh: usize,
+    pub material: Option<usize>,
+}
+
+/// A borrowed view of a node of a [`Scene`].
+#[derive(Clone, Copy)]
+pub struct SceneNodeRef<'a> {
+    scene: &'a Scene,
+    index: i32,
+}
+
+impl<'a> SceneNodeRef<'a> {
+    /// Returns the index of the node in the scene.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    pub fn name(&self) -> String {
+        ffi::draco_extra::scene_node_name(&self.scene.0, self.index)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    pub fn transform(&self) -> Transform {
+        let mut is_set = [false; 4];
+        let mut matrix = [0.0; 16];
+        let mut translation = [0.0; 3];
+        let mut rotation = [0.0; 4];
+        let mut scale = [0.0; 3];
+        unsafe {
+            ffi::draco_extra::scene_node_transform(
+                &self.scene.0,
+                self.index,
+                is_set.as_mut_ptr(),
+                matrix.as_mut_ptr(),
+                translation.as_mut_ptr(),
+                rotation.as_mut_ptr(),
+                scale.as_mut_ptr(),
+            );
+        }
+        Transform {
+            matrix: is_set[0].then_some(matrix),
+            translation: is_set[1].then_some(translation),
+            rotation: is_set[2].then_some(rotation),
+            scale: is_set[3].then_some(scale),
+        }
+    }
+
+    /// Returns the local transform as a column-major matrix, combining the
+    /// translation, rotation and scale if no matrix is set.
+    pub fn transformation_matrix(&self) -> [f64; 16] {
+        let mut matrix = [0.0; 16];
+        unsafe {
+            ffi::draco_extra::scene_node_transformation_matrix(
+                &self.scene.0,
+                self.index,
+                matrix.as_mut_ptr(),
+            );
+        }
+        matrix
+    }
+
+    /// Returns the index of the mesh group drawn at this node, if any.
+    pub fn mesh_group(&self) -> Option<usize> {
+        let group = ffi::draco_extra::scene_node_mesh_group(&self.scene.0, self.index);
+        usize::try_from(group).ok()
+    }
+
+    /// Iterates over the indices of the children of the node.
+    pub fn children(&self) -> impl Iterator<Item = usize> + 'a {
+        let (scene, index) = (self.scene, self.index);
+        (0..ffi::draco_extra::scene_node_num_children(&scene.0, index))
+            .map(move |i| ffi::draco_extra::scene_node_child(&scene.0, index, i) as usize)
+    }
+
+    /// Iterates over the indices of the parents of the node.
+    pub fn parents(&self) -> impl Iterator<Item = usize> + 'a {
+        let (scene, index) = (self.scene, self.index);
+        (0..ffi::draco_extra::scene_node_num_parents(&scene.0, index))
+            .map(move |i| ffi::draco_extra::scene_node_parent(&scene.0, index, i) as usize)
+    }
+}
+
+/// A borrowed view of a mesh group of a [`Scene`].
+#[derive(Clone, Copy)]
+pub struct MeshGroupRef<'a> {
+    scene: &'a Scene,
+    index: i32,
+}
+
+impl<'a> MeshGroupRef<'a> {
+    /// Returns the index of the mesh group in the scene.
+    pub fn index(&self) -> usize {
+        self.index as usize
+    }
+
+    pub fn name(&self) -> String {
+        ffi::draco_extra::scene_mesh_group_name(&self.scene.0, self.index)
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    pub fn num_mesh_instances(&self) -> usize {
+        ffi::draco_extra::scene_mesh_group_num_mesh_instances(&self.scene.0, self.index) as usize
+    }
+
+    pub fn mesh_instances(&self) -> impl Iterator<Item = MeshInstance> + 'a {
+        let (scene, index) = (self.scene, self.index);
+        (0..self.num_mesh_instances() as i32).map(move |i| {
+            let (mut mesh, mut material) = (0, 0);
+            unsafe {
+                ffi::draco_extra::scene_mesh_group_mesh_instance(
+                    &scene.0,
+                    index,
+                    i,
+                    &mut mesh,
+                    &mut material,
+                );
+            }
+            MeshInstance {
+                mesh: mesh as usize,
+                material: usize::try_from(material).ok(),
+            }
+        })
+    }
+}
+
+impl Default for Scene {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scene {
+    pub fn new() -> Self {
+        Self(ffi::draco::Scene::new().within_unique_ptr())
+    }
+
+    /// Reads a glTF or GLB file, keeping its scene graph.
+    pub fn read_gltf(path: impl AsRef<Path>) -> DracoResult<Self> {
+        let path = path_to_str(path.as_ref(), Operation::ReadFile)?;
+        let mut decoder = ffi::draco::GltfDecoder::new().within_unique_ptr();
+        let mut status_or = decoder.pin_mut().DecodeFromFileToScene(path);
+        Ok(Self(status_or.pin_mut().into_result(Operation::ReadFile)?))
+    }
+
+    /// Decodes GLB data from memory, see [`Self::read_gltf`]. Resources
+    /// must be embedded in the binary buffer.
+    pub fn from_glb_bytes(data: &[u8]) -> DracoResult<Self> {
+        let mut buffer = DecoderBuffer::from_buffer(data);
+        let mut decoder = ffi::draco::GltfDecoder::new().within_unique_ptr();
+        let mut status_or = unsafe {
+            decoder
+                .pin_mut()
+                .DecodeFromBufferToScene(buffer.as_mut_ptr())
+        };
+        Ok(Self(status_or.pin_mut().into_result(Operation::Decode)?))
+    }
+
+    /// Enables compression with the given options for all the meshes of the
+    /// scene, or disables it with `None`. See [`Mesh::set_compression_options`].
+    pub fn set_compression_options(
+        &mut self,
+        options: Option<&DracoCompressionOptions>,
+    ) -> DracoResult<()> {
+        let options = options.map(DracoCompressionOptions::to_draco).transpose()?;
+        let options_ptr = options.as_ref().map_or(std::ptr::null(), |options| {
+            options.as_ref().unwrap() as *const _
+        });
+        unsafe { ffi::draco_extra::scene_set_compression_options(self.0.pin_mut(), options_ptr) };
+        Ok(())
+    }
+
+    pub fn num_meshes(&self) -> usize {
+        self.0.NumMeshes().0 as usize
+    }
+
+    /// Adds a mesh to the scene, returning its index. The mesh is only drawn
+    /// once it is part of a mesh group placed by a node.
+    pub fn add_mesh(&mut self, mesh: Mesh) -> usize {
+        ffi::draco_extra::scene_add_mesh(self.0.pin_mut(), mesh.0) as usize
+    }
+
+    /// Returns a copy of the i-th mesh of the scene, e.g. to encode it on
+    /// its own.
+    pub fn mesh(&self, index: usize) -> Option<Mesh> {
+        let index = i32::try_from(index).ok()?;
+        let mesh = ffi::draco_extra::scene_copy_mesh(&self.0, index);
+        if mesh.is_null() {
+            None
+        } else {
+            Some(Mesh(mesh))
+        }
+    }
+
+    // Checks that `index` refers to one of the `len` elements of a list,
+    // returning it as a draco index.
+    fn check_index(
+        index: usize,
+        len: usize,
+        error: impl FnOnce() -> SceneError,
+    ) -> Result<i32, SceneError> {
+        if index < len {
+            Ok(index as i32)
+        } else {
+            Err(error())
+        }
+    }
+
+    fn check_node(&self, node: usize) -> Result<i32, SceneError> {
+        let num_nodes = self.num_nodes();
+        Self::check_index(node, num_nodes, || SceneError::NodeOutOfRange {
+            node,
+            num_nodes,
+        })
+    }
+
+    fn check_mesh_group(&self, mesh_group: usize) -> Result<i32, SceneError> {
+        let num_mesh_groups = self.num_mesh_groups();
+        Self::check_index(mesh_group, num_mesh_groups, || {
+            SceneError::MeshGroupOutOfRange {
+                mesh_group,
+                num_mesh_groups,
+            }
+        })
+    }
+
+    pub fn num_mesh_groups(&self) -> usize {
+        self.0.NumMeshGroups().0 as usize
+    }
+
+    /// Adds an empty mesh group, returning its index.
+    pub fn add_mesh_group(&mut self, name: &str) -> usize {
+        ffi::draco_extra::scene_add_mesh_group(self.0.pin_mut(), name) as usize
+    }
+
+    pub fn mesh_group(&self, index: usize) -> Option<MeshGroupRef<'_>> {
+        let index = self.check_mesh_group(index).ok()?;
+        Some(MeshGroupRef { scene: self, index })
+    }
+
+    pub fn mesh_groups(&self) -> impl Iterator<Item = MeshGroupRef<'_>> {
+        (0..self.num_mesh_groups() as i32).map(|index| MeshGroupRef { scene: self, index })
+    }
+
+    /// Adds a mesh, drawn with the given material, to a mesh group.
+    pub fn add_mesh_instance(
+        &mut self,
+        mesh_group: usize,
+        instance: MeshInstance,
+    ) -> Result<(), SceneError> {
+        let mesh_group = self.check_mesh_group(mesh_group)?;
+        let num_meshes = self.num_meshes();
+        let mesh = Self::check_index(instance.mesh, num_meshes, || SceneError::MeshOutOfRange {
+            mesh: instance.mesh,
+            num_meshes,
+        })?;
+        let material = match instance.material {
+            Some(material) => {
+                let num_materials = self.num_materials();
+                Self::check_index(material, num_materials, || SceneError::MaterialOutOfRange {
+                    material,
+                    num_materials,
+                })?
+            }
+            None => -1,
+        };
+        ffi::draco_extra::scene_mesh_group_add_mesh_instance(
+            self.0.pin_mut(),
+            mesh_group,
+            mesh,
+            material,
+        );
+        Ok(())
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.0.NumNodes().0 as usize
+    }
+
+    /// Adds a node with the identity transform, returning its index. The
+    /// node is only part of the scene graph once it is a root node or the
+    /// child of another node.
+    pub fn add_node(&mut self, name: &str) -> usize {
+        ffi::draco_extra::scene_add_node(self.0.pin_mut(), name) as usize
+    }
+
+    pub fn node(&self, index: usize) -> Option<SceneNodeRef<'_>> {
+        let index = self.check_node(index).ok()?;
+        Some(SceneNodeRef { scene: self, index })
+    }
+
+    /// Iterates over all nodes, whether or not they are reachable from the
+    /// root nodes.
+    pub fn nodes(&self) -> impl Iterator<Item = SceneNodeRef<'_>> {
+        (0..self.num_nodes() as i32).map(|index| SceneNodeRef { scene: self, index })
+    }
+
+    pub fn set_node_transform(
+        &mut self,
+        node: usize,
+        transform: &Transform,
+    ) -> Result<(), SceneError> {
+        fn as_ptr<const N: usize>(part: &Option<[f64; N]>) -> *const f64 {
+            part.as_ref().map_or(std::ptr::null(), |part| part.as_ptr())
+        }
+        let node = self.check_node(node)?;
+        unsafe {
+            ffi::draco_extra::scene_node_set_transform(
+                self.0.pin_mut(),
+                node,
+                as_ptr(&transform.matrix),
+                as_ptr(&transform.translation),
+                as_ptr(&transform.rotation),
+                as_ptr(&transform.scale),
+            );
+        }
+        Ok(())
+    }
+
+    /// Places a mesh group at a node, or removes it with `None`.
+    pub fn set_node_mesh_group(
+        &mut self,
+        node: usize,
+        mesh_group: Option<usize>,
+    ) -> Result<(), SceneError> {
+        let node = self.check_node(node)?;
+        let mesh_group = match mesh_group {
+            Some(mesh_group) => self.check_mesh_group(mesh_group)?,
+            None => -1,
+        };
+        ffi::draco_extra::scene_node_set_mesh_group(self.0.pin_mut(), node, mesh_group);
+        Ok(())
+    }
+
+    /// Makes `child` a child of `parent`. A node may have several parents,
+    /// but the graph must not contain cycles: linking a node to itself or to
+    /// one of its ancestors fails with [`SceneError::Cycle`].
+    pub fn add_child(&mut self, parent: usize, child: usize) -> Result<(), SceneError> {
+        let draco_parent = self.check_node(parent)?;
+        let draco_child = self.check_node(child)?;
+        if self.is_ancestor_or_self(child, parent) {
+            return Err(SceneError::Cycle { parent, child });
+        }
+        ffi::draco_extra::scene_add_child(self.0.pin_mut(), draco_parent, draco_child);
+        Ok(())
+    }
+
+    // Returns whether `ancestor` is `node` or one of its ancestors, walking up
+    // through all the parents of `node`.
+    fn is_ancestor_or_self(&self, ancestor: usize, node: usize) -> bool {
+        let mut visited = vec![false; self.num_nodes()];
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            if node == ancestor {
+                return true;
+            }
+            if !std::mem::replace(&mut visited[node], true) {
+                stack.extend(self.node(node).unwrap().parents());
+            }
+        }
+        false
+    }
+
+    pub fn num_root_nodes(&self) -> usize {
+        self.0.NumRootNodes().0 as usize
+    }
+
+    /// Iterates over the indices of the root nodes.
+    pub fn root_nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.num_root_nodes() as i32)
+            .map(|i| ffi::draco_extra::scene_root_node(&self.0, i) as usize)
+    }
+
+    pub fn add_root_node(&mut self, node: usize) -> Result<(), SceneError> {
+        let node = self.check_node(node)?;
+        ffi::draco_extra::scene_add_root_node(self.0.pin_mut(), node);
+        Ok(())
+    }
+
+    pub fn num_materials(&self) -> usize {
+        ffi::draco_extra::scene_num_materials(&self.0) as usize
+    }
+
+    /// Adds a material with draco's default properties, returning its
+    /// index.
+    pub fn add_material(&mut self, name: &str) -> usize {
+        ffi::draco_extra::scene_add_material(self.0.pin_mut(), name) as usize
+    }
+
+    pub fn material_name(&self, index: usize) -> Option<String> {
+        if index >= self.num_materials() {
+            return None;
+        }
+        Some(
+            ffi::draco_extra::scene_material_name(&self.0, index as i32)
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+}
@@ -0,0 +1,104 @@
+use crate::{
+    gltf::{DracoCompressionOptions, GltfEncoder},
+    io::path_to_str,
+    prelude::*,
+    scene::Scene,
+};
+use autocxx::prelude::*;
+use std::path::{Path, PathBuf};
+
+/// Options of a [`Transcoder`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TranscodingOptions {
+    /// Compression applied to every mesh of the transcoded scenes
+    pub geometry: DracoCompressionOptions,
+}
+
+/// Files read and written by [`Transcoder::transcode_files`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileOptions {
+    /// glTF or GLB file to transcode
+    pub input: PathBuf,
+    /// Output file: GLB if its extension is `.glb`, glTF otherwise
+    pub output: PathBuf,
+    /// Where to write the binary data of a glTF output, next to `output` by
+    /// default.
+    pub output_bin: Option<PathBuf>,
+    /// Where to write the textures of a glTF output, next to `output` by
+    /// default. Only used together with `output_bin`.
+    pub output_resource_directory: Option<PathBuf>,
+}
+
+/// Compresses the meshes of glTF files with the `KHR_draco_mesh_compression`
+/// extension, like the `draco_transcoder` tool but without leaving the
+/// process.
+///
+/// A transcoder can be reused for any number of files.
+///
+/// ```ignore
+/// let transcoder = Transcoder::new(TranscodingOptions::default())?;
+/// transcoder.transcode("input.gltf", "output.glb")?;
+/// ```
+pub struct Transcoder {
+    transcoder: UniquePtr<ffi::draco::DracoTranscoder>,
+    options: TranscodingOptions,
+}
+
+impl Transcoder {
+    /// Creates a transcoder, failing if the options are out of range.
+    pub fn new(options: TranscodingOptions) -> DracoResult<Self> {
+        let geometry = options.geometry.to_draco()?;
+        let mut status_or = ffi::draco_extra::new_draco_transcoder(&geometry);
+        let transcoder = status_or.pin_mut().into_result(Operation::Encode)?;
+        Ok(Self {
+            transcoder,
+            options,
+        })
+    }
+
+    pub fn options(&self) -> &TranscodingOptions {
+        &self.options
+    }
+
+    /// Transcodes the glTF or GLB file `input` into `output`, see
+    /// [`FileOptions`].
+    pub fn transcode(
+        &mut self,
+        input: impl AsRef<Path>,
+        output: impl AsRef<Path>,
+    ) -> DracoResult<()> {
+        self.transcode_files(&FileOptions {
+            input: input.as_ref().to_owned(),
+            output: output.as_ref().to_owned(),
+            ..Default::default()
+        })
+    }
+
+    /// Transcodes a file, choosing where each part of the output is written.
+    ///
+    /// Failures to read the input and to write the output are both reported
+    /// as [`Operation::Encode`] errors, as draco does not tell them apart.
+    pub fn transcode_files(&mut self, files: &FileOptions) -> DracoResult<()> {
+        let optional_path = |path: &Option<PathBuf>| match path {
+            Some(path) => path_to_str(path, Operation::WriteFile),
+            None => Ok(""),
+        };
+        let status = ffi::draco_extra::draco_transcoder_transcode(
+            self.transcoder.pin_mut(),
+            path_to_str(&files.input, Operation::ReadFile)?,
+            path_to_str(&files.output, Operation::WriteFile)?,
+            optional_path(&files.output_bin)?,
+            optional_path(&files.output_resource_directory)?,
+        )
+        .within_box();
+        DracoError::check(&status, Operation::Encode)
+    }
+
+    /// Transcodes GLB data from memory into GLB data. Resources must be
+    /// embedded in the binary buffer.
+    pub fn transcode_glb(&self, data: &[u8]) -> DracoResult<Vec<u8>> {
+        let mut scene = Scene::from_glb_bytes(data)?;
+        scene.set_compression_options(Some(&self.options.geometry))?;
+        GltfEncoder::new().encode_to_vec(&scene)
+    }
+}
@@ -1,33 +1,141 @@
 use crate::prelude::ffi::{self};
 use autocxx::prelude::*;
 
-pub struct DracoStatus(pub(crate) UniquePtr<ffi::draco::Status>);
+/// The Rust-side operation during which a draco error occurred.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Operation {
+    Encode,
+    Decode,
+    ReadFile,
+    WriteFile,
+}
 
-impl std::fmt::Display for DracoStatus {
+impl std::fmt::Display for Operation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.0.as_ref() {
-            Some(status) => write!(f, "{:?}", status.error_msg_string()),
-            None => write!(f, "unable to access underlying draco status"),
+        match self {
+            Self::Encode => write!(f, "encode"),
+            Self::Decode => write!(f, "decode"),
+            Self::ReadFile => write!(f, "read file"),
+            Self::WriteFile => write!(f, "write file"),
         }
     }
 }
 
-impl std::fmt::Debug for DracoStatus {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "DracoStatus({})", self)
+/// An error reported by draco, one variant per non-OK `draco::Status::Code`.
+///
+/// Unlike the underlying `draco::Status`, the error owns its message and can
+/// be matched on, e.g. to tell an unsupported bitstream version apart from
+/// corrupt input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DracoError {
+    /// General error (`DRACO_ERROR`), e.g. corrupt input
+    Draco {
+        operation: Operation,
+        message: String,
+    },
+    /// Error when handling an input or output stream (`IO_ERROR`)
+    Io {
+        operation: Operation,
+        message: String,
+    },
+    /// Invalid parameter passed to a function (`INVALID_PARAMETER`)
+    InvalidParameter {
+        operation: Operation,
+        message: String,
+    },
+    /// Input not compatible with the current version (`UNSUPPORTED_VERSION`)
+    UnsupportedVersion {
+        operation: Operation,
+        message: String,
+    },
+    /// Input was created with an unknown version of the library (`UNKNOWN_VERSION`)
+    UnknownVersion {
+        operation: Operation,
+        message: String,
+    },
+    /// Input contains a feature that is not supported (`UNSUPPORTED_FEATURE`)
+    UnsupportedFeature {
+        operation: Operation,
+        message: String,
+    },
+}
+
+impl DracoError {
+    /// Converts a draco status into a result, attaching the operation that
+    /// produced it.
+    pub(crate) fn check(status: &ffi::draco::Status, operation: Operation) -> DracoResult<()> {
+        let message = status.error_msg_string().to_string();
+        Err(match status.code() {
+            ffi::draco::Status_Code::OK => return Ok(()),
+            ffi::draco::Status_Code::IO_ERROR => Self::Io { operation, message },
+            ffi::draco::Status_Code::INVALID_PARAMETER => {
+                Self::InvalidParameter { operation, message }
+            }
+            ffi::draco::Status_Code::UNSUPPORTED_VERSION => {
+                Self::UnsupportedVersion { operation, message }
+            }
+            ffi::draco::Status_Code::UNKNOWN_VERSION => Self::UnknownVersion { operation, message },
+            ffi::draco::Status_Code::UNSUPPORTED_FEATURE => {
+                Self::UnsupportedFeature { operation, message }
+            }
+            _ => Self::Draco { operation, message },
+        })
+    }
+
+    pub fn operation(&self) -> Operation {
+        match self {
+            Self::Draco { operation, .. }
+            | Self::Io { operation, .. }
+            | Self::InvalidParameter { operation, .. }
+            | Self::UnsupportedVersion { operation, .. }
+            | Self::UnknownVersion { operation, .. }
+            | Self::UnsupportedFeature { operation, .. } => *operation,
+        }
+    }
+
+    /// Returns the error message reported by draco
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Draco { message, .. }
+            | Self::Io { message, .. }
+            | Self::InvalidParameter { message, .. }
+            | Self::UnsupportedVersion { message, .. }
+            | Self::UnknownVersion { message, .. }
+            | Self::UnsupportedFeature { message, .. } => message,
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Draco { .. } => "draco error",
+            Self::Io { .. } => "I/O error",
+            Self::InvalidParameter { .. } => "invalid parameter",
+            Self::UnsupportedVersion { .. } => "unsupported version",
+            Self::UnknownVersion { .. } => "unknown version",
+            Self::UnsupportedFeature { .. } => "unsupported feature",
+        }
     }
 }
 
-impl From<UniquePtr<ffi::draco::Status>> for DracoStatus {
-    fn from(status: UniquePtr<ffi::draco::Status>) -> Self {
-        Self(status)
+impl std::fmt::Display for DracoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} failed ({}): {}",
+            self.operation(),
+            self.kind(),
+            self.message()
+        )
     }
 }
 
-// allow turning DracoStatusType into std::error::Error
-impl std::error::Error for DracoStatus {}
+impl std::error::Error for DracoError {}
+
+pub type DracoResult<T> = Result<T, DracoError>;
 
-pub type DracoStatusType<T> = Result<T, DracoStatus>;
+#[deprecated(note = "use `DracoResult` instead")]
+pub type DracoStatusType<T> = DracoResult<T>;
 
 // This is a wrapper around the attribute id returned by the C++ API
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -0,0 +1,150 @@
+use draco_rs::{
+    mesh::{Mesh, MeshBuilder},
+    prelude::{ffi::draco::GeometryAttribute_Type, *},
+};
+
+#[test]
+fn add_attribute_to_empty_mesh() {
+    let mut mesh = Mesh::from_indices::<u32>(0, &[]).unwrap();
+    let values: &[[f32; 3]] = &[];
+    let result = mesh.add_attribute(GeometryAttribute_Type::POSITION, values);
+    assert!(matches!(result, Err(AttributeError::NotAdded(_))));
+    assert_eq!(mesh.num_attributes(), 0);
+}
+
+#[test]
+fn add_attribute_with_invalid_type() {
+    let mut mesh = Mesh::from_indices::<u32>(3, &[0, 1, 2]).unwrap();
+    let values = [[0.0f32; 3]; 3];
+    let result = mesh.add_attribute(GeometryAttribute_Type::INVALID, &values);
+    assert!(matches!(result, Err(AttributeError::NotAdded(_))));
+    assert_eq!(mesh.num_attributes(), 0);
+}
+
+#[test]
+fn named_attribute_lookups_reject_invalid_input() {
+    let mut mesh = Mesh::from_indices::<u32>(3, &[0, 1, 2]).unwrap();
+    let values = [[0.0f32; 3]; 3];
+    let attr_id = mesh
+        .add_attribute(GeometryAttribute_Type::POSITION, &values)
+        .unwrap();
+    let unique_id = mesh.attribute(attr_id.id()).unwrap().unique_id();
+
+    assert_eq!(
+        mesh.get_named_attribute_id(GeometryAttribute_Type::POSITION, 0),
+        Some(attr_id.id())
+    );
+    assert_eq!(
+        mesh.get_named_attribute_id(GeometryAttribute_Type::POSITION, 1),
+        None
+    );
+    assert!(mesh
+        .named_attribute(GeometryAttribute_Type::INVALID, 0)
+        .is_none());
+    for attr_type in [
+        GeometryAttribute_Type::INVALID,
+        GeometryAttribute_Type::NAMED_ATTRIBUTES_COUNT,
+    ] {
+        assert!(mesh
+            .named_attribute_by_unique_id(attr_type, unique_id)
+            .is_none());
+    }
+    assert!(mesh
+        .named_attribute_by_unique_id(GeometryAttribute_Type::POSITION, unique_id)
+        .is_some());
+}
+
+#[test]
+fn read_values_in_bulk_and_in_place() {
+    let mut mesh = Mesh::from_indices::<u32>(3, &[0, 1, 2]).unwrap();
+    let positions = [[0.0f32, 0.5, 1.0], [2.0, 3.0, 4.0], [-1.0, -2.0, -3.0]];
+    let position = mesh
+        .add_attribute(GeometryAttribute_Type::POSITION, &positions)
+        .unwrap();
+    let colors = [[1u16, 2], [3, 4], [5, 6]];
+    let color = mesh
+        .add_attribute(GeometryAttribute_Type::COLOR, &colors)
+        .unwrap();
+
+    assert_eq!(mesh.get_points(position).unwrap(), positions);
+    assert_eq!(mesh.get_points(color).unwrap(), colors);
+    assert_eq!(
+        mesh.get_points_slice(position).unwrap(),
+        positions.concat().as_slice()
+    );
+    assert_eq!(
+        mesh.get_points_slice(color).unwrap(),
+        colors.concat().as_slice()
+    );
+    let attribute = mesh.attribute(position.id()).unwrap();
+    assert_eq!(attribute.as_slice::<f32>().unwrap(), positions.concat());
+    assert_eq!(attribute.get_values::<f32, 3>().unwrap(), positions);
+    for (point, value) in positions.iter().enumerate() {
+        assert_eq!(attribute.get_value::<f32, 3>(point).unwrap(), *value);
+    }
+}
+
+#[test]
+fn read_values_with_wrong_layout() {
+    let mut mesh = Mesh::from_indices::<u32>(3, &[0, 1, 2]).unwrap();
+    let positions = [[0.0f32; 3]; 3];
+    mesh.add_attribute(GeometryAttribute_Type::POSITION, &positions)
+        .unwrap();
+    let attribute = mesh.attribute(AttrId(0)).unwrap();
+    assert!(matches!(
+        attribute.as_slice::<f64>(),
+        Err(AttributeError::DataTypeMismatch { .. })
+    ));
+    assert!(matches!(
+        attribute.get_values::<f32, 2>(),
+        Err(AttributeError::ComponentCountMismatch {
+            expected: 3,
+            actual: 2
+        })
+    ));
+}
+
+#[test]
+fn read_bool_values() {
+    let mut mesh = Mesh::from_indices::<u32>(3, &[0, 1, 2]).unwrap();
+    let flags = [[true], [false], [true]];
+    let flag = mesh
+        .add_attribute(GeometryAttribute_Type::GENERIC, &flags)
+        .unwrap();
+    assert_eq!(mesh.get_points(flag).unwrap(), flags);
+    // draco may store any byte for `true`, so bools are never borrowed
+    assert!(matches!(
+        mesh.get_points_slice(flag),
+        Err(AttributeError::NotContiguous)
+    ));
+}
+
+#[test]
+fn read_values_of_deduplicated_mesh() {
+    let mut builder = MeshBuilder::new(2).unwrap();
+    let position = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    let faces = [
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+        [[1.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+    ];
+    for (face, corners) in faces.iter().enumerate() {
+        builder
+            .set_attribute_values_for_face(position, face, corners)
+            .unwrap();
+    }
+    let mesh = builder.build().unwrap();
+    // the two shared corners are merged
+    assert_eq!(mesh.num_points(), 4);
+
+    let points = mesh.get_points(position).unwrap();
+    assert_eq!(points.len(), 4);
+    let attribute = mesh.attribute(position.id()).unwrap();
+    for (point, value) in points.iter().enumerate() {
+        assert_eq!(attribute.get_value::<f32, 3>(point).unwrap(), *value);
+    }
+    for (face, corners) in mesh.faces().zip(faces) {
+        assert_eq!(face.map(|point| points[point as usize]), corners);
+    }
+}
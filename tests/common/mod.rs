@@ -0,0 +1,23 @@
+// Not every test crate uses every helper.
+#![allow(dead_code)]
+
+use draco_rs::{
+    mesh::Mesh,
+    prelude::{ffi::draco::GeometryAttribute_Type, *},
+};
+use std::path::PathBuf;
+
+pub fn triangle() -> Mesh {
+    let mut mesh = Mesh::from_indices::<u32>(3, &[0, 1, 2]).unwrap();
+    let positions = [[0.0f32, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    mesh.add_attribute(GeometryAttribute_Type::POSITION, &positions)
+        .unwrap();
+    mesh
+}
+
+// A directory of its own for every test, as tests run in parallel.
+pub fn output_dir(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("draco-rs-{}-{}", test, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
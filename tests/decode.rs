@@ -0,0 +1,78 @@
+use draco_rs::{
+    pointcloud::*,
+    prelude::{ffi::draco::GeometryAttribute_Type, *},
+};
+
+fn encode_point_cloud(positions: &[[f32; 3]], intensities: &[[u16; 1]]) -> Vec<u8> {
+    let mut builder = PointCloudBuilder::new(positions.len() as u32);
+    let position = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    let intensity = builder
+        .add_attribute::<u16, 1>(GeometryAttribute_Type::GENERIC)
+        .unwrap();
+    builder.set_attribute_values(position, positions).unwrap();
+    builder
+        .set_attribute_values(intensity, intensities)
+        .unwrap();
+    let pc = builder.build(false);
+    // without quantization, sequential encoding keeps the values and their order
+    let mut encoder = Encoder::new().set_encoding_method(PointCloudEncodingMethod::Sequential);
+    let buffer = pc.to_buffer(&mut encoder).unwrap();
+    buffer.as_slice().to_vec()
+}
+
+// The returned buffer outlives the vector it was created from.
+fn owned_buffer(positions: &[[f32; 3]], intensities: &[[u16; 1]]) -> DecoderBuffer<'static> {
+    let data = encode_point_cloud(positions, intensities);
+    DecoderBuffer::from_owned(data)
+}
+
+#[test]
+fn decode_from_owned_buffer() {
+    let positions = [[0.0f32, 1.0, 2.0], [3.5, -4.5, 5.25], [6.0, 7.0, 8.0]];
+    let intensities = [[10u16], [20], [30]];
+    let size = encode_point_cloud(&positions, &intensities).len();
+
+    let mut buffer = owned_buffer(&positions, &intensities);
+    assert_eq!(buffer.remaining_size(), size as i64);
+    // reading the header does not consume the buffer
+    assert_eq!(
+        Decoder::get_encoded_geometry_type(&mut buffer).unwrap(),
+        EncodedGeometryType::PointCloud
+    );
+    assert_eq!(buffer.remaining_size(), size as i64);
+
+    let pc = Decoder::new()
+        .decode(&mut buffer)
+        .unwrap()
+        .into_pointcloud()
+        .unwrap();
+    assert_eq!(pc.num_points(), 3);
+    let position = TypedAttrId::<f32, 3>::new(
+        pc.get_named_attribute_id(GeometryAttribute_Type::POSITION, 0)
+            .unwrap(),
+    );
+    let intensity = TypedAttrId::<u16, 1>::new(
+        pc.get_named_attribute_id(GeometryAttribute_Type::GENERIC, 0)
+            .unwrap(),
+    );
+    assert_eq!(pc.get_points(position).unwrap(), positions);
+    assert_eq!(pc.get_points(intensity).unwrap(), intensities);
+}
+
+#[test]
+fn decode_from_borrowed_and_owned_data() {
+    let positions = [[1.0f32, 2.0, 3.0]];
+    let intensities = [[7u16]];
+    let data = encode_point_cloud(&positions, &intensities);
+
+    let mut borrowed = DecoderBuffer::from_buffer(&data);
+    let from_borrowed = PointCloud::from_buffer(&mut Decoder::new(), &mut borrowed).unwrap();
+    let mut owned = DecoderBuffer::from_owned(data.as_slice());
+    let from_owned = PointCloud::from_buffer(&mut Decoder::new(), &mut owned).unwrap();
+
+    let position = TypedAttrId::<f32, 3>::new(AttrId(0));
+    assert_eq!(from_borrowed.get_points(position).unwrap(), positions);
+    assert_eq!(from_owned.get_points(position).unwrap(), positions);
+}
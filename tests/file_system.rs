@@ -0,0 +1,151 @@
+mod common;
+
+use draco_rs::{
+    io::{register_file_system, FileSystem, Format},
+    mesh::Mesh,
+    prelude::*,
+};
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::Path,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+// A prefix that does not exist on disk, so that draco's own disk access
+// cannot serve the paths below.
+const PREFIX: &str = "draco-rs-virtual:";
+
+const TRIANGLE_OBJ: &str = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+
+type Files = Arc<Mutex<HashMap<String, Vec<u8>>>>;
+
+struct MemoryFileSystem(Files);
+
+struct MemoryFile {
+    files: Files,
+    path: String,
+}
+
+impl Write for MemoryFile {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut files = self.files.lock().unwrap();
+        files.get_mut(&self.path).unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        self.0.lock().unwrap().get(path).cloned()
+    }
+
+    fn create(&self, path: &str) -> Option<Box<dyn Write + Send>> {
+        if !path.starts_with(PREFIX) {
+            return None;
+        }
+        self.0.lock().unwrap().insert(path.to_owned(), Vec::new());
+        Some(Box::new(MemoryFile {
+            files: self.0.clone(),
+            path: path.to_owned(),
+        }))
+    }
+}
+
+// Fails on every access; registered first to check that draco moves on to
+// the next file system.
+struct PanickingFileSystem;
+
+impl FileSystem for PanickingFileSystem {
+    fn read(&self, path: &str) -> Option<Vec<u8>> {
+        panic!("cannot read {}", path)
+    }
+
+    fn create(&self, path: &str) -> Option<Box<dyn Write + Send>> {
+        panic!("cannot create {}", path)
+    }
+}
+
+// File systems are registered for the whole process, so all tests share
+// the same in-memory files.
+fn files() -> Files {
+    static FILES: OnceLock<Files> = OnceLock::new();
+    FILES
+        .get_or_init(|| {
+            let files = Files::default();
+            files.lock().unwrap().insert(
+                format!("{}/triangle.obj", PREFIX),
+                TRIANGLE_OBJ.as_bytes().to_vec(),
+            );
+            register_file_system(PanickingFileSystem);
+            register_file_system(MemoryFileSystem(files.clone()));
+            files
+        })
+        .clone()
+}
+
+#[test]
+fn read_from_file_system() {
+    files();
+    assert!(!Path::new(PREFIX).exists());
+    let mesh = Mesh::read_from_file(format!("{}/triangle.obj", PREFIX)).unwrap();
+    assert_eq!(mesh.num_faces(), 1);
+    assert_eq!(mesh.num_points(), 3);
+    let position = TypedAttrId::<f32, 3>::new(AttrId(0));
+    let points = mesh.get_points(position).unwrap();
+    let face = mesh.face(0u32).unwrap();
+    assert_eq!(
+        face.map(|point| points[point as usize]),
+        [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]
+    );
+
+    assert!(Mesh::read_from_file(format!("{}/missing.obj", PREFIX)).is_err());
+}
+
+// With the transcoder feature, draco creates the directory of the file on
+// disk before writing, so the write would never reach the file systems.
+#[cfg(not(feature = "transcoder"))]
+#[test]
+fn write_to_file_system() {
+    let files = files();
+    let path = format!("{}/output/triangle.drc", PREFIX);
+    let mesh = common::triangle();
+    mesh.write_to_file(&path, Format::Drc).unwrap();
+    assert!(!Path::new(PREFIX).exists());
+
+    let data = files.lock().unwrap().get(&path).cloned().unwrap();
+    assert_eq!(data, mesh.write_to_vec(Format::Drc).unwrap());
+    let decoded = Mesh::from_bytes(&data, Format::Drc).unwrap();
+    assert_eq!(decoded.num_faces(), 1);
+
+    // read it back through draco
+    let decoded = Mesh::read_from_file(&path).unwrap();
+    assert_eq!(decoded.num_faces(), 1);
+}
+
+#[test]
+fn disk_takes_precedence() {
+    let files = files();
+    let dir = common::output_dir("disk-takes-precedence");
+    let path = dir.join("triangle.obj");
+    std::fs::write(&path, TRIANGLE_OBJ).unwrap();
+    // the file system has a different mesh for the same path
+    files.lock().unwrap().insert(
+        path.to_str().unwrap().to_owned(),
+        b"v 0 0 0\nv 1 0 0\nv 0 1 0\nv 1 1 0\nf 1 2 3\nf 2 4 3\n".to_vec(),
+    );
+    let mesh = Mesh::read_from_file(&path).unwrap();
+    assert_eq!(mesh.num_faces(), 1);
+
+    // writes to an existing directory go to the disk as well
+    let output = dir.join("triangle.drc");
+    common::triangle()
+        .write_to_file(&output, Format::Drc)
+        .unwrap();
+    assert!(output.is_file());
+    assert!(!files.lock().unwrap().contains_key(output.to_str().unwrap()));
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "transcoder")]
+
+mod common;
+
+use common::{output_dir, triangle};
+use draco_rs::{gltf::GltfEncoder, mesh::Mesh, prelude::*};
+
+#[test]
+fn encode_glb_to_file() {
+    let dir = output_dir("encode_glb_to_file");
+    let path = dir.join("triangle.glb");
+    GltfEncoder::new()
+        .encode_to_file(&triangle(), &path)
+        .unwrap();
+
+    let mesh = Mesh::read_gltf(&path).unwrap();
+    assert_eq!(mesh.num_faces(), 1);
+    assert_eq!(mesh.num_points(), 3);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn encode_gltf_to_file() {
+    let dir = output_dir("encode_gltf_to_file");
+    let path = dir.join("triangle.gltf");
+    GltfEncoder::new()
+        .encode_to_file(&triangle(), &path)
+        .unwrap();
+    assert!(dir.join("triangle.bin").is_file());
+
+    let mesh = Mesh::read_gltf(&path).unwrap();
+    assert_eq!(mesh.num_faces(), 1);
+    std::fs::remove_dir_all(dir).unwrap();
+}
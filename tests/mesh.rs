@@ -0,0 +1,73 @@
+use draco_rs::{
+    mesh::MeshBuilder,
+    prelude::{ffi::draco::GeometryAttribute_Type, *},
+};
+
+#[test]
+fn builder_face_limit() {
+    // draco computes the number of corners, `3 * num_faces`, as an `i32`
+    assert_eq!(MeshBuilder::MAX_NUM_FACES, 715_827_882);
+    assert_eq!(MeshBuilder::MAX_NUM_FACES as i64 * 3, i32::MAX as i64 - 1);
+    // the limit itself is accepted, but would allocate gigabytes of faces,
+    // so only the counts above it are built here
+    for num_faces in [MeshBuilder::MAX_NUM_FACES + 1, i32::MAX as u32, u32::MAX] {
+        assert!(matches!(
+            MeshBuilder::new(num_faces),
+            Err(AttributeError::TooManyFaces(n)) if n == num_faces
+        ));
+    }
+
+    // a rejected count leaves the builder as it was
+    let mut builder = MeshBuilder::new(2).unwrap();
+    assert!(builder.start(MeshBuilder::MAX_NUM_FACES + 1).is_err());
+    assert_eq!(builder.num_faces(), 2);
+}
+
+#[test]
+fn builder_rejects_invalid_attribute_types() {
+    let mut builder = MeshBuilder::new(1).unwrap();
+    assert!(matches!(
+        builder.add_attribute::<f32, 3>(GeometryAttribute_Type::INVALID),
+        Err(AttributeError::NotAdded(_))
+    ));
+    assert!(matches!(
+        builder
+            .add_named_attribute::<f32, 3>(GeometryAttribute_Type::NAMED_ATTRIBUTES_COUNT, "count"),
+        Err(AttributeError::NotAdded(_))
+    ));
+    let position = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    assert_eq!(position.id(), AttrId(0));
+    let corners = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    builder
+        .set_attribute_values_for_face(position, 0u32, &corners)
+        .unwrap();
+    let mesh = builder.build().unwrap();
+    assert_eq!(mesh.num_attributes(), 1);
+    assert_eq!(mesh.num_faces(), 1);
+    let points: Vec<[f32; 3]> = mesh.get_points(position).unwrap();
+    let face = mesh.face(0u32).unwrap();
+    assert_eq!(face.map(|point| points[point as usize]), corners);
+}
+
+#[test]
+fn builder_names_attributes() {
+    let mut builder = MeshBuilder::new(1).unwrap();
+    assert!(builder
+        .add_named_attribute::<f32, 3>(GeometryAttribute_Type::INVALID, "invalid")
+        .is_err());
+    let position = builder
+        .add_named_attribute::<f32, 3>(GeometryAttribute_Type::POSITION, "position")
+        .unwrap();
+    let corners = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]];
+    builder
+        .set_attribute_values_for_face(position, 0u32, &corners)
+        .unwrap();
+    let mesh = builder.build().unwrap();
+    assert_eq!(
+        mesh.attribute_by_name("position").unwrap().unique_id(),
+        mesh.attribute(position.id()).unwrap().unique_id()
+    );
+    assert!(mesh.attribute_by_name("invalid").is_none());
+}
@@ -0,0 +1,94 @@
+mod common;
+
+use common::triangle;
+use draco_rs::{mesh::Mesh, prelude::*};
+
+fn sample_metadata() -> Metadata {
+    let mut lod = Metadata::new();
+    lod.add_int("level", 2);
+    lod.add_double_array("distances", &[10.0, 25.5]);
+    let mut source = Metadata::new();
+    source.add_string("tool", "scanner");
+    assert!(source.add_sub_metadata("lod", lod));
+
+    let mut metadata = Metadata::new();
+    metadata.add_int("version", -3);
+    metadata.add_int_array("tile", &[1, 2, 3]);
+    metadata.add_double("scale", 0.125);
+    metadata.add_string("title", "triangle");
+    metadata.add_binary("blob", &[0, 255, 7]);
+    assert!(metadata.add_sub_metadata("source", source));
+    metadata
+}
+
+fn round_trip(mesh: &Mesh) -> Mesh {
+    let buffer = mesh.to_buffer(&mut Encoder::new()).unwrap();
+    let mut buffer = DecoderBuffer::from_encoder_buffer(&buffer);
+    Mesh::from_buffer(&mut Decoder::new(), &mut buffer).unwrap()
+}
+
+#[test]
+fn metadata_round_trip() {
+    let mut mesh = triangle();
+    assert!(mesh.metadata().is_none());
+    let metadata = sample_metadata();
+    mesh.add_metadata(&metadata);
+    let mut attribute_metadata = Metadata::new();
+    attribute_metadata.add_string("unit", "meter");
+    mesh.add_attribute_metadata(AttrId(0), &attribute_metadata)
+        .unwrap();
+
+    let decoded = round_trip(&mesh);
+    let decoded_metadata = decoded.metadata().unwrap();
+    assert_eq!(decoded_metadata, metadata);
+    assert_eq!(decoded_metadata.get_int("version"), Some(-3));
+    assert_eq!(decoded_metadata.get_int_array("tile"), Some(vec![1, 2, 3]));
+    assert_eq!(decoded_metadata.get_double("scale"), Some(0.125));
+    assert_eq!(decoded_metadata.get_string("title"), Some("triangle"));
+    assert_eq!(decoded_metadata.get_binary("blob"), Some(&[0, 255, 7][..]));
+    let lod = decoded_metadata
+        .sub_metadata("source")
+        .and_then(|source| source.sub_metadata("lod"))
+        .unwrap();
+    assert_eq!(lod.get_int("level"), Some(2));
+    assert_eq!(lod.get_double_array("distances"), Some(vec![10.0, 25.5]));
+
+    assert_eq!(
+        decoded.attribute_metadata(AttrId(0)),
+        Some(attribute_metadata)
+    );
+}
+
+#[test]
+fn attribute_name_round_trip() {
+    let mut mesh = triangle();
+    mesh.set_attribute_name(AttrId(0), "position").unwrap();
+    assert!(mesh.set_attribute_name(AttrId(1), "missing").is_err());
+
+    let decoded = round_trip(&mesh);
+    assert_eq!(decoded.attribute_id_by_name("position"), Some(AttrId(0)));
+    assert!(decoded.attribute_by_name("missing").is_none());
+    // the name is stored as a regular attribute metadata entry
+    assert_eq!(
+        decoded
+            .attribute_metadata(AttrId(0))
+            .unwrap()
+            .get_string("name"),
+        Some("position")
+    );
+    assert_eq!(decoded.metadata().unwrap_or_default().num_entries(), 0);
+}
+
+#[test]
+fn typed_getters_check_sizes() {
+    let metadata = sample_metadata();
+    // an `i32` entry cannot be read as a `f64`, and the other way around
+    assert_eq!(metadata.get_double("version"), None);
+    assert_eq!(metadata.get_int("scale"), None);
+    assert_eq!(metadata.get_int("missing"), None);
+    assert!(!metadata.add_sub_metadata("source", Metadata::new()));
+    assert_eq!(
+        metadata.sub_metadata("source").unwrap().get_string("tool"),
+        Some("scanner")
+    );
+}
@@ -0,0 +1,306 @@
+use draco_rs::{
+    pointcloud::*,
+    prelude::{ffi::draco::GeometryAttribute_Type, *},
+};
+
+#[test]
+fn growable_builder_rejects_invalid_attribute_types() {
+    let mut builder = GrowablePointCloudBuilder::new();
+    for attr_type in [
+        GeometryAttribute_Type::INVALID,
+        GeometryAttribute_Type::NAMED_ATTRIBUTES_COUNT,
+    ] {
+        assert!(matches!(
+            builder.add_attribute::<f32, 3>(attr_type),
+            Err(AttributeError::NotAdded(_))
+        ));
+    }
+    let position = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    assert_eq!(position.id(), AttrId(0));
+    builder
+        .extend([[1.0, 2.0, 3.0]], |point, value| point.set(position, value))
+        .unwrap();
+    let pc = builder.build(false);
+    assert_eq!(pc.num_attributes(), 1);
+    assert_eq!(pc.get_points(position).unwrap(), [[1.0, 2.0, 3.0]]);
+}
+
+#[test]
+fn growable_builder_without_points() {
+    let mut builder = GrowablePointCloudBuilder::new();
+    let position = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    let pc = builder.build(false);
+    assert_eq!(pc.num_points(), 0);
+    assert_eq!(pc.get_points(position).unwrap(), Vec::<[f32; 3]>::new());
+}
+
+#[test]
+fn builder_rejects_invalid_attribute_types() {
+    let mut builder = PointCloudBuilder::new(2);
+    assert!(matches!(
+        builder.add_attribute::<f32, 3>(GeometryAttribute_Type::INVALID),
+        Err(AttributeError::NotAdded(_))
+    ));
+    assert!(matches!(
+        builder
+            .add_named_attribute::<f32, 3>(GeometryAttribute_Type::NAMED_ATTRIBUTES_COUNT, "count"),
+        Err(AttributeError::NotAdded(_))
+    ));
+    // the rejected attributes must not shift the layout of the next one
+    let normal = builder
+        .add_attribute::<u8, 2>(GeometryAttribute_Type::NORMAL)
+        .unwrap();
+    assert_eq!(normal.id(), AttrId(0));
+    builder.add_point(normal, 0, &[1, 2]).unwrap();
+    builder.add_point(normal, 1, &[3, 4]).unwrap();
+    let pc = builder.build(false);
+    assert_eq!(pc.num_attributes(), 1);
+    assert_eq!(pc.get_points(normal).unwrap(), [[1, 2], [3, 4]]);
+}
+
+#[test]
+fn builder_names_attributes() {
+    let mut builder = PointCloudBuilder::new(1);
+    assert!(builder
+        .add_named_attribute::<f32, 3>(GeometryAttribute_Type::INVALID, "invalid")
+        .is_err());
+    let position = builder
+        .add_named_attribute::<f32, 3>(GeometryAttribute_Type::POSITION, "position")
+        .unwrap();
+    let color = builder
+        .add_named_attribute::<u8, 4>(GeometryAttribute_Type::COLOR, "color")
+        .unwrap();
+    builder.add_point(position, 0, &[1.0, 2.0, 3.0]).unwrap();
+    builder.add_point(color, 0, &[255, 0, 0, 255]).unwrap();
+    let pc = builder.build(true);
+    assert_eq!(pc.num_attributes(), 2);
+    assert_eq!(
+        pc.attribute_by_name("position").unwrap().unique_id(),
+        pc.attribute(position.id()).unwrap().unique_id()
+    );
+    assert_eq!(
+        pc.attribute_by_name("color").unwrap().unique_id(),
+        pc.attribute(color.id()).unwrap().unique_id()
+    );
+    assert!(pc.attribute_by_name("invalid").is_none());
+}
+
+#[repr(C)]
+struct Vertex {
+    // so that no attribute starts at the beginning of the vertex
+    _id: u32,
+    position: [f32; 3],
+    normal: [f32; 3],
+    uv: [u16; 2],
+}
+
+fn vertices() -> Vec<Vertex> {
+    (0..4u16)
+        .map(|i| {
+            let f = i as f32;
+            Vertex {
+                _id: i as u32,
+                position: [f, f + 0.5, -f],
+                normal: [0.0, 0.0, if i % 2 == 0 { 1.0 } else { -1.0 }],
+                uv: [i, 100 + i],
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn builder_sets_values_in_bulk() {
+    let positions = [[1.0f64, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    let mut builder = PointCloudBuilder::new(2);
+    let position = builder
+        .add_attribute::<f64, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    assert!(matches!(
+        builder.set_attribute_values(position, &positions[..1]),
+        Err(AttributeError::ValueCountMismatch {
+            expected: 2,
+            actual: 1
+        })
+    ));
+    builder.set_attribute_values(position, &positions).unwrap();
+    let pc = builder.build(false);
+    assert_eq!(pc.get_points(position).unwrap(), positions);
+}
+
+#[test]
+fn builder_sets_strided_values() {
+    let vertices = vertices();
+    let mut builder = PointCloudBuilder::new(vertices.len() as u32);
+    let position = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    let normal = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::NORMAL)
+        .unwrap();
+    let uv = builder
+        .add_attribute::<u16, 2>(GeometryAttribute_Type::TEX_COORD)
+        .unwrap();
+    builder
+        .set_attribute_values_strided(position, &vertices, |v| &v.position)
+        .unwrap();
+    builder
+        .set_attribute_values_strided(normal, &vertices, |v| &v.normal)
+        .unwrap();
+    // the last field of the struct, to catch offsets that run past the vertex
+    builder
+        .set_attribute_values_strided(uv, &vertices, |v| &v.uv)
+        .unwrap();
+    let pc = builder.build(false);
+
+    assert_eq!(
+        pc.get_points(position).unwrap(),
+        vertices.iter().map(|v| v.position).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        pc.get_points(normal).unwrap(),
+        vertices.iter().map(|v| v.normal).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        pc.get_points(uv).unwrap(),
+        vertices.iter().map(|v| v.uv).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn builder_rejects_fields_outside_the_vertex() {
+    let vertices = vertices();
+    let outside = [1.0f32, 2.0, 3.0];
+    let mut builder = PointCloudBuilder::new(vertices.len() as u32);
+    let position = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    assert!(matches!(
+        builder.set_attribute_values_strided(position, &vertices, |_| &outside),
+        Err(AttributeError::NotContiguous)
+    ));
+    assert!(matches!(
+        builder.set_attribute_values_strided(position, &vertices[1..], |v| &v.position),
+        Err(AttributeError::ValueCountMismatch { .. })
+    ));
+}
+
+struct Record {
+    position: [f32; 3],
+    intensity: u16,
+    classified: bool,
+}
+
+#[test]
+fn growable_builder_collects_records() {
+    let mut builder = GrowablePointCloudBuilder::new();
+    let position = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    let intensity = builder
+        .add_attribute::<u16, 1>(GeometryAttribute_Type::GENERIC)
+        .unwrap();
+    let classified = builder
+        .add_attribute::<bool, 1>(GeometryAttribute_Type::GENERIC)
+        .unwrap();
+    let records: Vec<Record> = (0..5u16)
+        .map(|i| Record {
+            position: [i as f32, 2.0 * i as f32, -(i as f32)],
+            intensity: 1000 + i,
+            classified: i % 2 == 0,
+        })
+        .collect();
+    // records arrive in several batches
+    for batch in records.chunks(2) {
+        builder
+            .extend(batch, |point, record| {
+                point.set(intensity, [record.intensity])?;
+                point.set(position, record.position)?;
+                point.set(classified, [record.classified])
+            })
+            .unwrap();
+    }
+    assert_eq!(builder.num_points(), 5);
+    let pc = builder.build(false);
+    assert_eq!(pc.num_points(), 5);
+    assert_eq!(
+        pc.get_points(position).unwrap(),
+        records.iter().map(|r| r.position).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        pc.get_points(intensity).unwrap(),
+        records.iter().map(|r| [r.intensity]).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        pc.get_points(classified).unwrap(),
+        records.iter().map(|r| [r.classified]).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn growable_builder_discards_incomplete_points() {
+    let mut builder = GrowablePointCloudBuilder::new();
+    let position = builder
+        .add_attribute::<f32, 3>(GeometryAttribute_Type::POSITION)
+        .unwrap();
+    let intensity = builder
+        .add_attribute::<u16, 1>(GeometryAttribute_Type::GENERIC)
+        .unwrap();
+    builder
+        .extend([1.0f32], |point, x| {
+            point.set(position, [x, x, x])?;
+            point.set(intensity, [1])
+        })
+        .unwrap();
+
+    // the second record misses its intensity, so it and later records are
+    // dropped, but the first one is kept
+    let result = builder.extend([2.0f32, 3.0, 4.0], |point, x| {
+        point.set(position, [x, x, x])?;
+        if x != 3.0 {
+            point.set(intensity, [x as u16])?;
+        }
+        Ok(())
+    });
+    assert!(matches!(result, Err(AttributeError::MissingValue(id)) if id == intensity.id()));
+    assert_eq!(builder.num_points(), 2);
+
+    // a value of the wrong layout is rejected without touching the point
+    let other = TypedAttrId::<f64, 3>::new(position.id());
+    let result = builder.extend([5.0f32], |point, _| point.set(other, [0.0; 3]));
+    assert!(matches!(
+        result,
+        Err(AttributeError::DataTypeMismatch { .. })
+    ));
+    let missing = TypedAttrId::<f32, 3>::new(AttrId(2));
+    let result = builder.extend([5.0f32], |point, _| point.set(missing, [0.0; 3]));
+    assert!(matches!(result, Err(AttributeError::NotFound(AttrId(2)))));
+    assert_eq!(builder.num_points(), 2);
+
+    let pc = builder.build(false);
+    assert_eq!(
+        pc.get_points(position).unwrap(),
+        [[1.0, 1.0, 1.0], [2.0, 2.0, 2.0]]
+    );
+    assert_eq!(pc.get_points(intensity).unwrap(), [[1], [2]]);
+}
+
+#[test]
+fn collect_positions_into_point_cloud() {
+    let positions = vec![[1i32, 2], [3, 4], [5, 6]];
+    let pc: PointCloud = positions.iter().copied().collect();
+    assert_eq!(pc.num_points(), 3);
+    assert_eq!(pc.num_attributes(), 1);
+    let position = TypedAttrId::<i32, 2>::new(AttrId(0));
+    assert_eq!(pc.get_points(position).unwrap(), positions);
+    assert!(matches!(
+        pc.attribute(AttrId(0)).unwrap().attribute_type(),
+        GeometryAttribute_Type::POSITION
+    ));
+
+    let empty: PointCloud = std::iter::empty::<[f32; 3]>().collect();
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "transcoder")]
+
+use draco_rs::scene::{Scene, SceneError};
+
+#[test]
+fn add_child_rejects_self_parenting() {
+    let mut scene = Scene::new();
+    let node = scene.add_node("node");
+    assert_eq!(
+        scene.add_child(node, node),
+        Err(SceneError::Cycle {
+            parent: node,
+            child: node
+        })
+    );
+    assert_eq!(scene.node(node).unwrap().children().count(), 0);
+}
+
+#[test]
+fn add_child_rejects_cycles() {
+    let mut scene = Scene::new();
+    let a = scene.add_node("a");
+    let b = scene.add_node("b");
+    let c = scene.add_node("c");
+    scene.add_child(a, b).unwrap();
+    scene.add_child(b, c).unwrap();
+    assert_eq!(
+        scene.add_child(c, a),
+        Err(SceneError::Cycle {
+            parent: c,
+            child: a
+        })
+    );
+    assert_eq!(scene.node(c).unwrap().children().count(), 0);
+}
+
+#[test]
+fn add_child_accepts_shared_children() {
+    let mut scene = Scene::new();
+    let root = scene.add_node("root");
+    let left = scene.add_node("left");
+    let right = scene.add_node("right");
+    let shared = scene.add_node("shared");
+    scene.add_child(root, left).unwrap();
+    scene.add_child(root, right).unwrap();
+    scene.add_child(left, shared).unwrap();
+    scene.add_child(right, shared).unwrap();
+    assert_eq!(
+        scene.node(shared).unwrap().parents().collect::<Vec<_>>(),
+        [left, right]
+    );
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "transcoder")]
+
+mod common;
+
+use common::{output_dir, triangle};
+use draco_rs::{
+    gltf::GltfEncoder,
+    mesh::Mesh,
+    transcoder::{FileOptions, Transcoder, TranscodingOptions},
+};
+
+const DRACO_EXTENSION: &[u8] = b"KHR_draco_mesh_compression";
+
+fn contains(data: &[u8], pattern: &[u8]) -> bool {
+    data.windows(pattern.len()).any(|window| window == pattern)
+}
+
+#[test]
+fn transcode_file() {
+    let dir = output_dir("transcode_file");
+    let input = dir.join("input.gltf");
+    let output = dir.join("output.glb");
+    GltfEncoder::new()
+        .encode_to_file(&triangle(), &input)
+        .unwrap();
+
+    let mut transcoder = Transcoder::new(TranscodingOptions::default()).unwrap();
+    transcoder.transcode(&input, &output).unwrap();
+
+    assert!(contains(&std::fs::read(&output).unwrap(), DRACO_EXTENSION));
+    let mesh = Mesh::read_gltf(&output).unwrap();
+    assert_eq!(mesh.num_faces(), 1);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn transcode_files_with_separate_bin() {
+    let dir = output_dir("transcode_files_with_separate_bin");
+    let input = dir.join("input.gltf");
+    GltfEncoder::new()
+        .encode_to_file(&triangle(), &input)
+        .unwrap();
+
+    let files = FileOptions {
+        input,
+        output: dir.join("output.gltf"),
+        output_bin: Some(dir.join("data.bin")),
+        output_resource_directory: None,
+    };
+    let mut transcoder = Transcoder::new(TranscodingOptions::default()).unwrap();
+    transcoder.transcode_files(&files).unwrap();
+
+    assert!(contains(
+        &std::fs::read(&files.output).unwrap(),
+        DRACO_EXTENSION
+    ));
+    assert!(dir.join("data.bin").is_file());
+    let mesh = Mesh::read_gltf(&files.output).unwrap();
+    assert_eq!(mesh.num_faces(), 1);
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn transcode_glb() {
+    let input = GltfEncoder::new().encode_to_vec(&triangle()).unwrap();
+    let transcoder = Transcoder::new(TranscodingOptions::default()).unwrap();
+    let output = transcoder.transcode_glb(&input).unwrap();
+
+    assert!(contains(&output, DRACO_EXTENSION));
+    let mesh = Mesh::from_glb_bytes(&output).unwrap();
+    assert_eq!(mesh.num_faces(), 1);
+}
+
+#[test]
+fn transcode_missing_input() {
+    let dir = output_dir("transcode_missing_input");
+    let mut transcoder = Transcoder::new(TranscodingOptions::default()).unwrap();
+    assert!(transcoder
+        .transcode(dir.join("missing.gltf"), dir.join("output.glb"))
+        .is_err());
+    assert!(!dir.join("output.glb").exists());
+    std::fs::remove_dir_all(dir).unwrap();
+}